@@ -0,0 +1,5 @@
+fn main() {
+    // Only the Node.js addon needs extra linker arguments
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
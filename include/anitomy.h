@@ -0,0 +1,183 @@
+#ifndef ANITOMY_H
+#define ANITOMY_H
+
+/* Warning: this file is generated by cbindgen from src/capi.rs. Do not modify it manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * How confident the parser is that an element is correct.
+ *
+ * This is based on the rule that produced the element. For example, an episode
+ * found in `S01E03` has a `High` confidence while an episode that was found by
+ * falling back to the last number in the name has a `Low` confidence. Elements that
+ * come from an ambiguous keyword (e.g. `ESP`) have a lower confidence than those from
+ * an unambiguous one (e.g. `x264`).
+ *
+ * Confidences are ordered, so `confidence >= Confidence::Medium` can be used to filter elements.
+ */
+typedef enum AnitomyConfidence {
+  /**
+   * The element was found by a fallback heuristic and should be reviewed
+   */
+  ANITOMY_CONFIDENCE_LOW = 0,
+  /**
+   * The element was found by a heuristic that is usually correct
+   */
+  ANITOMY_CONFIDENCE_MEDIUM = 1,
+  /**
+   * The element was found by an unambiguous pattern or keyword
+   */
+  ANITOMY_CONFIDENCE_HIGH = 2,
+} AnitomyConfidence;
+
+/**
+ * The kind of element that has been parsed.
+ *
+ * The discriminants are stable, new kinds are only ever added at the end.
+ */
+typedef enum AnitomyElementKind {
+  ANITOMY_ELEMENT_KIND_AUDIO_TERM = 0,
+  ANITOMY_ELEMENT_KIND_DEVICE_COMPATIBILITY = 1,
+  ANITOMY_ELEMENT_KIND_EPISODE = 2,
+  ANITOMY_ELEMENT_KIND_EPISODE_TITLE = 3,
+  ANITOMY_ELEMENT_KIND_EPISODE_ALT = 4,
+  ANITOMY_ELEMENT_KIND_FILE_CHECKSUM = 5,
+  ANITOMY_ELEMENT_KIND_FILE_EXTENSION = 6,
+  ANITOMY_ELEMENT_KIND_LANGUAGE = 7,
+  ANITOMY_ELEMENT_KIND_OTHER = 8,
+  ANITOMY_ELEMENT_KIND_RELEASE_GROUP = 9,
+  ANITOMY_ELEMENT_KIND_RELEASE_INFORMATION = 10,
+  ANITOMY_ELEMENT_KIND_RELEASE_VERSION = 11,
+  ANITOMY_ELEMENT_KIND_SEASON = 12,
+  ANITOMY_ELEMENT_KIND_SOURCE = 13,
+  ANITOMY_ELEMENT_KIND_SUBTITLES = 14,
+  ANITOMY_ELEMENT_KIND_TITLE = 15,
+  ANITOMY_ELEMENT_KIND_TYPE = 16,
+  ANITOMY_ELEMENT_KIND_VIDEO_RESOLUTION = 17,
+  ANITOMY_ELEMENT_KIND_VIDEO_TERM = 18,
+  ANITOMY_ELEMENT_KIND_VOLUME = 19,
+  ANITOMY_ELEMENT_KIND_YEAR = 20,
+  ANITOMY_ELEMENT_KIND_DATE = 21,
+} AnitomyElementKind;
+
+/**
+ * The elements of a parsed string.
+ *
+ * This is created by `anitomy_parse` and must be freed with `anitomy_elements_free`.
+ */
+typedef struct AnitomyElements AnitomyElements;
+
+/**
+ * The options used when parsing.
+ *
+ * Use `anitomy_options_default` to get the defaults, where everything is enabled.
+ */
+typedef struct AnitomyOptions {
+  bool episode;
+  bool episode_title;
+  bool file_checksum;
+  bool file_extension;
+  bool release_group;
+  bool season;
+  bool title;
+  bool video_resolution;
+  bool year;
+  bool date;
+} AnitomyOptions;
+
+/**
+ * A parsed element.
+ *
+ * The value is owned by the `AnitomyElements` it came from and is valid until
+ * that is freed.
+ */
+typedef struct AnitomyElement {
+  AnitomyElementKind kind;
+  AnitomyConfidence confidence;
+  /**
+   * The NUL-terminated UTF-8 value of the element
+   */
+  const char *value;
+  /**
+   * The length of the value in bytes, without the NUL terminator
+   */
+  size_t value_len;
+  /**
+   * The byte offset in the input where the element starts
+   */
+  size_t start;
+  /**
+   * The byte offset in the input where the element ends
+   */
+  size_t end;
+} AnitomyElement;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns the default options, where everything is enabled.
+ */
+AnitomyOptions anitomy_options_default(void);
+
+/**
+ * Parses a NUL-terminated UTF-8 string into its elements.
+ *
+ * If `options` is `NULL` then the default options are used. Returns `NULL` if `input`
+ * is `NULL` or not valid UTF-8.
+ *
+ * # Safety
+ *
+ * `input` must be `NULL` or point to a NUL-terminated string, and `options` must be
+ * `NULL` or point to a valid `AnitomyOptions`.
+ */
+AnitomyElements *anitomy_parse(const char *input, const AnitomyOptions *options);
+
+/**
+ * Returns the number of parsed elements, or 0 if `elements` is `NULL`.
+ *
+ * # Safety
+ *
+ * `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed.
+ */
+size_t anitomy_elements_len(const AnitomyElements *elements);
+
+/**
+ * Writes the element at `index` to `out`, in the order they appear in the input.
+ *
+ * Returns `false` if the index is out of bounds or any of the pointers are `NULL`,
+ * in which case `out` is left untouched.
+ *
+ * # Safety
+ *
+ * `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed,
+ * and `out` must be `NULL` or point to writable memory for an `AnitomyElement`.
+ */
+bool anitomy_elements_get(const AnitomyElements *elements, size_t index, AnitomyElement *out);
+
+/**
+ * Frees the elements returned by `anitomy_parse`. Does nothing if `elements` is `NULL`.
+ *
+ * # Safety
+ *
+ * `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed.
+ */
+void anitomy_elements_free(AnitomyElements *elements);
+
+/**
+ * Returns the human readable name of the kind as a static NUL-terminated string,
+ * e.g. `Release Group`. Returns `NULL` if `kind` is not an `AnitomyElementKind`.
+ */
+const char *anitomy_element_kind_name(uint32_t kind);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ANITOMY_H */
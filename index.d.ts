@@ -0,0 +1,84 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+
+/**
+ * The kind of element that has been parsed.
+ *
+ * The discriminants are stable, new kinds are only ever added at the end.
+ */
+export const enum ElementKind {
+  AudioTerm = 0,
+  DeviceCompatibility = 1,
+  Episode = 2,
+  EpisodeTitle = 3,
+  EpisodeAlt = 4,
+  FileChecksum = 5,
+  FileExtension = 6,
+  Language = 7,
+  Other = 8,
+  ReleaseGroup = 9,
+  ReleaseInformation = 10,
+  ReleaseVersion = 11,
+  Season = 12,
+  Source = 13,
+  Subtitles = 14,
+  Title = 15,
+  Type = 16,
+  VideoResolution = 17,
+  VideoTerm = 18,
+  Volume = 19,
+  Year = 20,
+  Date = 21
+}
+/**
+ * How confident the parser is that an element is correct.
+ *
+ * This is based on the rule that produced the element. For example, an episode
+ * found in `S01E03` has a [`Confidence::High`] confidence while an episode that
+ * was found by falling back to the last number in the name has a [`Confidence::Low`]
+ * confidence. Elements that come from an ambiguous keyword (e.g. `ESP`) have a lower
+ * confidence than those from an unambiguous one (e.g. `x264`).
+ *
+ * Confidences are ordered, so `confidence >= Confidence::Medium` can be used to filter elements.
+ */
+export const enum Confidence {
+  /** The element was found by a fallback heuristic and should be reviewed */
+  Low = 0,
+  /** The element was found by a heuristic that is usually correct */
+  Medium = 1,
+  /** The element was found by an unambiguous pattern or keyword */
+  High = 2
+}
+/** The options used when parsing, everything that is missing is enabled. */
+export interface Options {
+  episode?: boolean
+  episode_title?: boolean
+  file_checksum?: boolean
+  file_extension?: boolean
+  release_group?: boolean
+  season?: boolean
+  title?: boolean
+  video_resolution?: boolean
+  year?: boolean
+  date?: boolean
+}
+/**
+ * A parsed element.
+ *
+ * The span is in UTF-16 code units, so `input.slice(start, end)` works as expected.
+ */
+export interface Element {
+  kind: ElementKind
+  value: string
+  start: number
+  end: number
+  confidence: Confidence
+}
+/**
+ * Parses a string into its element components.
+ *
+ * If `options` is missing then the default options are used.
+ */
+export function parse(input: string, options?: Options | undefined | null): Array<Element>
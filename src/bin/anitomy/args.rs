@@ -0,0 +1,336 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use anitomy::{Options, Template};
+
+use crate::{output::Format, rename::Rename};
+
+/// The usage of the `--no-*` flags, which are shared by every command
+macro_rules! toggle_usage {
+    () => {
+        "      --no-episodes           Don't parse episodes
+      --no-episode-titles     Don't parse episode titles
+      --no-file-checksums     Don't parse file checksums
+      --no-file-extensions    Don't parse file extensions
+      --no-release-groups     Don't parse release groups
+      --no-seasons            Don't parse seasons
+      --no-titles             Don't parse titles
+      --no-video-resolutions  Don't parse video resolutions
+      --no-years              Don't parse years
+      --no-dates              Don't parse dates
+"
+    };
+}
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: anitomy [OPTIONS] [NAME]...
+       anitomy rename [OPTIONS] --template <TEMPLATE> <FILE>...
+
+Parses anime video file names. If no names are given then they are read from
+stdin, one per line.
+
+Commands:
+  rename  Renames files based on their parsed names, see `anitomy rename --help`
+
+Options:
+  -f, --format <FORMAT>       The output format: json, jsonl, tsv or table [default: table]
+",
+    toggle_usage!(),
+    "  -h, --help                  Print help
+  -V, --version               Print version
+"
+);
+
+pub const RENAME_USAGE: &str = concat!(
+    "\
+Usage: anitomy rename [OPTIONS] --template <TEMPLATE> <FILE>...
+
+Renames files based on their parsed names. The new name of a file is rendered from
+the template, e.g. '{title} - {episode:02}.{file_extension}', and stays in the same
+directory. Every rename is printed as a diff.
+
+Nothing is renamed if any of the new names collide with each other or with an
+existing file.
+
+Options:
+  -t, --template <TEMPLATE>   The template for the new names
+  -r, --require <FIELDS>      Skips files where any of the comma separated fields are missing
+  -n, --dry-run               Prints the renames without renaming anything
+  -u, --undo-log <FILE>       Writes a new shell script that undoes the renames
+",
+    toggle_usage!(),
+    "  -h, --help                  Print help
+"
+);
+
+/// A builder method of [`Options`]
+type Toggle = fn(Options, bool) -> Options;
+
+/// The flags for each of the [`Options`] builder methods
+const TOGGLES: [(&str, Toggle); 10] = [
+    ("--no-episodes", Options::episodes),
+    ("--no-episode-titles", Options::episode_titles),
+    ("--no-file-checksums", Options::file_checksums),
+    ("--no-file-extensions", Options::file_extensions),
+    ("--no-release-groups", Options::release_groups),
+    ("--no-seasons", Options::seasons),
+    ("--no-titles", Options::titles),
+    ("--no-video-resolutions", Options::video_resolutions),
+    ("--no-years", Options::years),
+    ("--no-dates", Options::dates),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Parse {
+        format: Format,
+        options: Options,
+        names: Vec<String>,
+    },
+    Rename(Rename),
+    Help(&'static str),
+    Version,
+}
+
+enum Arg {
+    /// A flag with its value if it was given as `--flag=value`
+    Flag(String, Option<String>),
+    Positional(String),
+}
+
+/// Splits the arguments into flags and positional arguments.
+///
+/// Everything after `--` is a positional argument.
+struct Args<I> {
+    args: I,
+    positional: bool,
+}
+
+fn into_string(arg: OsString) -> Result<String, String> {
+    arg.into_string()
+        .map_err(|arg| format!("'{}' is not valid UTF-8", arg.to_string_lossy()))
+}
+
+impl<I: Iterator<Item = OsString>> Args<I> {
+    fn next(&mut self) -> Result<Option<Arg>, String> {
+        let Some(arg) = self.args.next() else {
+            return Ok(None);
+        };
+        let arg = into_string(arg)?;
+        if self.positional || !arg.starts_with('-') || arg == "-" {
+            return Ok(Some(Arg::Positional(arg)));
+        }
+        if arg == "--" {
+            self.positional = true;
+            return self.next();
+        }
+        match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                Ok(Some(Arg::Flag(flag.to_owned(), Some(value.to_owned()))))
+            }
+            _ => Ok(Some(Arg::Flag(arg, None))),
+        }
+    }
+
+    /// Returns the value of the flag, either given inline or as the next argument
+    fn value(&mut self, flag: &str, inline: Option<String>) -> Result<String, String> {
+        match inline {
+            Some(value) => Ok(value),
+            None => self
+                .args
+                .next()
+                .ok_or_else(|| format!("a value is required for '{flag}'"))
+                .and_then(into_string),
+        }
+    }
+}
+
+/// Applies one of the `--no-*` flags to the options
+fn toggle(options: Options, flag: &str, value: Option<&str>) -> Result<Options, String> {
+    match TOGGLES.iter().find(|(name, _)| *name == flag) {
+        Some((_, toggle)) if value.is_none() => Ok(toggle(options, false)),
+        _ => Err(match value {
+            Some(value) => format!("unexpected argument '{flag}={value}'"),
+            None => format!("unexpected argument '{flag}'"),
+        }),
+    }
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    if args.peek().is_some_and(|arg| arg == "rename") {
+        args.next();
+        return parse_rename_args(args);
+    }
+
+    let mut format = Format::Table;
+    let mut options = Options::default();
+    let mut names = Vec::new();
+    let mut args = Args {
+        args,
+        positional: false,
+    };
+    while let Some(arg) = args.next()? {
+        let (flag, value) = match arg {
+            Arg::Positional(name) => {
+                names.push(name);
+                continue;
+            }
+            Arg::Flag(flag, value) => (flag, value),
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help(USAGE)),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-f" | "--format" => format = args.value(&flag, value)?.parse()?,
+            _ => options = toggle(options, &flag, value.as_deref())?,
+        }
+    }
+    Ok(Command::Parse {
+        format,
+        options,
+        names,
+    })
+}
+
+fn parse_rename_args(args: impl Iterator<Item = OsString>) -> Result<Command, String> {
+    let mut template = None;
+    let mut options = Options::default();
+    let mut required = Vec::new();
+    let mut dry_run = false;
+    let mut undo_log = None;
+    let mut files = Vec::new();
+    let mut args = Args {
+        args,
+        positional: false,
+    };
+    while let Some(arg) = args.next()? {
+        let (flag, value) = match arg {
+            Arg::Positional(file) => {
+                files.push(PathBuf::from(file));
+                continue;
+            }
+            Arg::Flag(flag, value) => (flag, value),
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help(RENAME_USAGE)),
+            "-t" | "--template" => {
+                let value = args.value(&flag, value)?;
+                let parsed = Template::parse(&value)
+                    .map_err(|e| format!("invalid template '{value}': {e}"))?;
+                template = Some(parsed);
+            }
+            "-r" | "--require" => {
+                for field in args.value(&flag, value)?.split(',').map(str::trim) {
+                    // Templates already know which field names are valid
+                    let is_name = field.bytes().all(|b| b.is_ascii_lowercase() || b == b'_');
+                    if !is_name || Template::parse(&format!("{{{field}}}")).is_err() {
+                        return Err(format!("unknown field '{field}' for '{flag}'"));
+                    }
+                    required.push(field.to_owned());
+                }
+            }
+            "-n" | "--dry-run" if value.is_none() => dry_run = true,
+            "-u" | "--undo-log" => undo_log = Some(PathBuf::from(args.value(&flag, value)?)),
+            _ => options = toggle(options, &flag, value.as_deref())?,
+        }
+    }
+
+    let template = template.ok_or("a template is required, e.g. '--template {title}'")?;
+    if files.is_empty() {
+        return Err("no files were given to rename".into());
+    }
+    Ok(Command::Rename(Rename {
+        template,
+        options,
+        required,
+        dry_run,
+        undo_log,
+        files,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse(&["-f", "tsv", "--no-episode-titles", "a", "--", "--no-years"]),
+            Ok(Command::Parse {
+                format: Format::Tsv,
+                options: Options::default().episode_titles(false),
+                names: vec!["a".into(), "--no-years".into()],
+            })
+        );
+        assert_eq!(
+            parse(&["--format=jsonl", "--no-dates", "--no-titles"]),
+            Ok(Command::Parse {
+                format: Format::JsonLines,
+                options: Options::default().dates(false).titles(false),
+                names: vec![],
+            })
+        );
+        assert_eq!(parse(&["a", "--help"]), Ok(Command::Help(USAGE)));
+        assert_eq!(parse(&["-V"]), Ok(Command::Version));
+        assert!(parse(&["--format"]).is_err());
+        assert!(parse(&["--format=yaml"]).is_err());
+        assert!(parse(&["--no-years=1"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rename_args() {
+        assert_eq!(
+            parse(&[
+                "rename",
+                "-t",
+                "{title}.{file_extension}",
+                "--require=title, episode",
+                "-n",
+                "--no-seasons",
+                "--undo-log",
+                "undo.sh",
+                "a.mkv",
+            ]),
+            Ok(Command::Rename(Rename {
+                template: Template::parse("{title}.{file_extension}").unwrap(),
+                options: Options::default().seasons(false),
+                required: vec!["title".into(), "episode".into()],
+                dry_run: true,
+                undo_log: Some("undo.sh".into()),
+                files: vec!["a.mkv".into()],
+            }))
+        );
+        assert_eq!(parse(&["rename", "-h"]), Ok(Command::Help(RENAME_USAGE)));
+        assert!(parse(&["rename", "a.mkv"]).is_err());
+        assert!(parse(&["rename", "-t", "{title}"]).is_err());
+        assert!(parse(&["rename", "-t", "{unknown}", "a.mkv"]).is_err());
+        assert!(parse(&["rename", "-t", "{title}", "-r", "unknown", "a.mkv"]).is_err());
+        assert!(parse(&["rename", "-t", "{title}", "-r", "title:02", "a.mkv"]).is_err());
+        assert!(parse(&["rename", "-t", "{title}", "--dry-run=yes", "a.mkv"]).is_err());
+        assert_eq!(
+            parse(&["--", "rename"]),
+            Ok(Command::Parse {
+                format: Format::Table,
+                options: Options::default(),
+                names: vec!["rename".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_usage_lists_toggles() {
+        for (flag, _) in TOGGLES {
+            assert!(USAGE.contains(flag), "{flag} is missing from the usage");
+            assert!(
+                RENAME_USAGE.contains(flag),
+                "{flag} is missing from the usage"
+            );
+        }
+    }
+}
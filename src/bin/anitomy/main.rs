@@ -0,0 +1,73 @@
+//! The `anitomy` command line tool.
+
+mod args;
+mod output;
+mod rename;
+
+use std::{
+    io::{self, BufRead, BufWriter},
+    process::ExitCode,
+};
+
+use anitomy::{Options, Parser};
+
+use crate::{
+    args::{parse_args, Command},
+    output::{Format, Output},
+};
+
+fn run(format: Format, options: Options, names: &[String]) -> io::Result<()> {
+    let parser = Parser::new().options(options);
+    let mut context = parser.context();
+    let mut output = Output::new(BufWriter::new(io::stdout().lock()), format);
+    if names.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let name = line.strip_suffix('\r').unwrap_or(&line);
+            output.write(name, &context.parse(name))?;
+        }
+    } else {
+        for name in names {
+            output.write(name, &context.parse(name))?;
+        }
+    }
+    output.finish()
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args_os().skip(1)) {
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("error: {error}\n\nFor more information, try '--help'.");
+            return ExitCode::from(2);
+        }
+    };
+    match command {
+        Command::Help(usage) => print!("{usage}"),
+        Command::Version => println!("anitomy {}", env!("CARGO_PKG_VERSION")),
+        Command::Parse {
+            format,
+            options,
+            names,
+        } => match run(format, options, &names) {
+            // Piping into something like `head` shouldn't be reported as an error
+            Err(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+                eprintln!("error: {error}");
+                return ExitCode::FAILURE;
+            }
+            _ => {}
+        },
+        Command::Rename(rename) => {
+            match rename.run(&mut io::stdout().lock(), &mut io::stderr().lock()) {
+                Ok(true) => {}
+                Ok(false) => return ExitCode::FAILURE,
+                Err(error) if error.kind() == io::ErrorKind::BrokenPipe => {}
+                Err(error) => {
+                    eprintln!("error: {error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
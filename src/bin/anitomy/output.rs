@@ -0,0 +1,181 @@
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use anitomy::{Element, ElementObject, OwnedElementObject};
+
+/// The format that the parsed elements are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A JSON array with an object per name, shaped like [`ElementObject`]
+    Json,
+    /// A JSON object per line, shaped like [`ElementObject`]
+    JsonLines,
+    /// A row per element with the name, kind, value and span, separated by tabs
+    Tsv,
+    /// A human readable table per name
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::JsonLines),
+            "tsv" => Ok(Self::Tsv),
+            "table" => Ok(Self::Table),
+            _ => Err(format!(
+                "invalid format '{s}', expected one of json, jsonl, tsv or table"
+            )),
+        }
+    }
+}
+
+/// Escapes the characters that would break a TSV row
+fn escape_tsv(s: &str) -> Cow<'_, str> {
+    if !s.contains(['\\', '\t', '\n', '\r']) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 2);
+    for ch in s.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(ch),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Writes the parsed elements of every name in the given format.
+///
+/// [`Output::finish`] has to be called once every name is written.
+pub struct Output<W: Write> {
+    writer: W,
+    format: Format,
+    count: usize,
+    // JSON arrays can only be written once every name is known
+    objects: Vec<OwnedElementObject>,
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(writer: W, format: Format) -> Self {
+        Self {
+            writer,
+            format,
+            count: 0,
+            objects: Vec::new(),
+        }
+    }
+
+    /// Writes the elements parsed from the name
+    pub fn write(&mut self, name: &str, elements: &[Element<'_>]) -> io::Result<()> {
+        match self.format {
+            Format::Json => self.objects.push(elements.iter().collect()),
+            Format::JsonLines => {
+                let object = elements.iter().collect::<ElementObject<'_>>();
+                serde_json::to_writer(&mut self.writer, &object).map_err(io::Error::other)?;
+                writeln!(self.writer)?;
+            }
+            Format::Tsv => {
+                if self.count == 0 {
+                    writeln!(self.writer, "name\tkind\tvalue\tstart\tend")?;
+                }
+                let name = escape_tsv(name);
+                for element in elements {
+                    let span = element.span();
+                    writeln!(
+                        self.writer,
+                        "{name}\t{}\t{}\t{}\t{}",
+                        element.kind().as_str(),
+                        escape_tsv(element.value()),
+                        span.start,
+                        span.end
+                    )?;
+                }
+            }
+            Format::Table => {
+                if self.count > 0 {
+                    writeln!(self.writer)?;
+                }
+                writeln!(self.writer, "{name}")?;
+                let width = elements
+                    .iter()
+                    .map(|e| e.kind().as_str().len())
+                    .max()
+                    .unwrap_or_default();
+                for element in elements {
+                    let kind = element.kind().as_str();
+                    writeln!(self.writer, "  {kind:<width$}  {}", element.value())?;
+                }
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Writes anything that is still pending and flushes the writer
+    pub fn finish(mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            serde_json::to_writer_pretty(&mut self.writer, &self.objects)
+                .map_err(io::Error::other)?;
+            writeln!(self.writer)?;
+        }
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_all(format: Format, names: &[&str]) -> String {
+        let mut buffer = Vec::new();
+        let mut output = Output::new(&mut buffer, format);
+        for name in names {
+            output.write(name, &anitomy::parse(name)).unwrap();
+        }
+        output.finish().unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn test_table() {
+        let names = ["[Group] Title - 01.mkv", "Title 02"];
+        assert_eq!(
+            write_all(Format::Table, &names),
+            "[Group] Title - 01.mkv\n  \
+            Release Group   Group\n  \
+            Title           Title\n  \
+            Episode         01\n  \
+            File Extension  mkv\n\
+            \n\
+            Title 02\n  \
+            Title    Title\n  \
+            Episode  02\n"
+        );
+    }
+
+    #[test]
+    fn test_tsv() {
+        assert_eq!(
+            write_all(Format::Tsv, &["Title\t- 01"]),
+            "name\tkind\tvalue\tstart\tend\n\
+            Title\\t- 01\tTitle\tTitle\t0\t5\n\
+            Title\\t- 01\tEpisode\t01\t8\t10\n"
+        );
+        assert_eq!(escape_tsv("a\\b\r\n"), "a\\\\b\\r\\n");
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("jsonl".parse(), Ok(Format::JsonLines));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
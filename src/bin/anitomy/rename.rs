@@ -0,0 +1,367 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{self, Path, PathBuf},
+};
+
+use anitomy::{Options, ParsedRelease, Parser, Template, TemplateFields};
+
+/// The `rename` command
+#[derive(Debug, PartialEq)]
+pub struct Rename {
+    pub template: Template,
+    pub options: Options,
+    /// The fields that have to be present for a file to be renamed
+    pub required: Vec<String>,
+    pub dry_run: bool,
+    /// Where to write a shell script that undoes the renames
+    pub undo_log: Option<PathBuf>,
+    pub files: Vec<PathBuf>,
+}
+
+/// A file that is going to be renamed
+#[derive(Debug, PartialEq, Eq)]
+struct Move {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// Quotes a path so that it can be used as a single argument in a shell script.
+///
+/// Paths that are not valid UTF-8 can't be quoted without changing them.
+fn shell_quote(path: &Path) -> Option<String> {
+    Some(format!("'{}'", path.to_str()?.replace('\'', r"'\''")))
+}
+
+impl Rename {
+    /// Returns the new path of the file, or why it's skipped.
+    ///
+    /// Files that would keep their name return `None`.
+    fn new_path(&self, parser: &Parser, path: &Path) -> Result<Option<PathBuf>, String> {
+        let name = path
+            .file_name()
+            .ok_or("it is not a file name")?
+            .to_str()
+            .ok_or("the name is not valid UTF-8")?;
+        let elements = parser.parse(name);
+        let release = elements.iter().collect::<ParsedRelease>();
+        let missing = self
+            .required
+            .iter()
+            .filter(|field| release.field(field).is_none())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(format!("{} could not be found", missing.join(", ")));
+        }
+
+        let rendered = self.template.render(&release);
+        if rendered.is_empty() || rendered == "." || rendered == ".." {
+            return Err(format!("the new name '{rendered}' is not a file name"));
+        }
+        if rendered.chars().any(std::path::is_separator) {
+            return Err(format!(
+                "the new name '{rendered}' contains a path separator"
+            ));
+        }
+        let to = path.with_file_name(rendered);
+        Ok((to != path).then_some(to))
+    }
+
+    /// Computes every rename, writing the files that are skipped to `err`
+    fn plan(&self, err: &mut impl Write) -> io::Result<Vec<Move>> {
+        let parser = Parser::new().options(self.options);
+        let mut moves = Vec::with_capacity(self.files.len());
+        for from in &self.files {
+            match self.new_path(&parser, from) {
+                Ok(Some(to)) => moves.push(Move {
+                    from: from.clone(),
+                    to,
+                }),
+                Ok(None) => {}
+                Err(reason) => writeln!(err, "skipped '{}': {reason}", from.display())?,
+            }
+        }
+        Ok(moves)
+    }
+
+    /// Runs the command, returning whether it was successful.
+    ///
+    /// The renames are written to `out` as a diff and everything else to `err`.
+    pub fn run(&self, out: &mut impl Write, err: &mut impl Write) -> io::Result<bool> {
+        let moves = self.plan(err)?;
+        if let Some(collisions) = find_collisions(&moves, |path| fs::symlink_metadata(path).is_ok())
+        {
+            for collision in collisions {
+                writeln!(err, "error: {collision}")?;
+            }
+            writeln!(err, "error: nothing was renamed because of collisions")?;
+            return Ok(false);
+        }
+
+        if let Some(log) = self.undo_log.as_ref().filter(|_| !self.dry_run) {
+            if let Some(error) = check_undo_log(log, &moves)? {
+                writeln!(err, "error: {error}")?;
+                writeln!(err, "error: nothing was renamed because of the undo log")?;
+                return Ok(false);
+            }
+        }
+
+        for Move { from, to } in &moves {
+            writeln!(out, "-{}\n+{}", from.display(), to.display())?;
+        }
+        out.flush()?;
+        if self.dry_run {
+            writeln!(err, "would rename {} file(s)", moves.len())?;
+            return Ok(true);
+        }
+
+        let mut undo_log = match &self.undo_log {
+            Some(path) => {
+                // Never overwrite anything, including a log from a previous run
+                let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+                let mut log = BufWriter::new(file);
+                writeln!(log, "#!/bin/sh")?;
+                writeln!(log, "# Undoes the renames done by `anitomy rename`")?;
+                Some(log)
+            }
+            None => None,
+        };
+        let mut renamed = 0;
+        for Move { from, to } in &moves {
+            // Check again in case the file was created after planning
+            let result = if fs::symlink_metadata(to).is_ok() {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "the file already exists",
+                ))
+            } else {
+                fs::rename(from, to)
+            };
+            if let Err(error) = result {
+                writeln!(err, "error: could not rename '{}': {error}", from.display())?;
+                break;
+            }
+            if let Some(log) = &mut undo_log {
+                // The script might be run from another directory, and the paths were
+                // already checked to be valid UTF-8
+                let (to, from) = (path::absolute(to)?, path::absolute(from)?);
+                if let Some((to, from)) = shell_quote(&to).zip(shell_quote(&from)) {
+                    writeln!(log, "mv -n -- {to} {from}")?;
+                }
+            }
+            renamed += 1;
+        }
+        if let Some(log) = &mut undo_log {
+            log.flush()?;
+        }
+        writeln!(err, "renamed {renamed} of {} file(s)", moves.len())?;
+        Ok(renamed == moves.len())
+    }
+}
+
+/// Describes why the undo log can't be written, if it can't.
+///
+/// The log can't already exist or be one of the renamed files, and every path has to be
+/// valid UTF-8 so that it can be written to the script as is.
+fn check_undo_log(log: &Path, moves: &[Move]) -> io::Result<Option<String>> {
+    if fs::symlink_metadata(log).is_ok() {
+        return Ok(Some(format!(
+            "the undo log '{}' already exists",
+            log.display()
+        )));
+    }
+    let log = path::absolute(log)?;
+    for Move { from, to } in moves {
+        for path in [from, to] {
+            let path = path::absolute(path)?;
+            if path == log {
+                return Ok(Some(format!(
+                    "the undo log '{}' would be renamed",
+                    log.display()
+                )));
+            }
+            if path.to_str().is_none() {
+                return Ok(Some(format!(
+                    "'{}' can't be written to the undo log since it's not valid UTF-8",
+                    path.display()
+                )));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Describes every new path that is used more than once or already exists
+fn find_collisions(moves: &[Move], exists: impl Fn(&Path) -> bool) -> Option<Vec<String>> {
+    let mut targets = HashMap::<&Path, &Path>::with_capacity(moves.len());
+    let mut collisions = Vec::new();
+    for Move { from, to } in moves {
+        if let Some(other) = targets.insert(to, from) {
+            collisions.push(format!(
+                "'{}' and '{}' would both be renamed to '{}'",
+                other.display(),
+                from.display(),
+                to.display()
+            ));
+        } else if exists(to) {
+            collisions.push(format!(
+                "'{}' would be renamed to '{}', which already exists",
+                from.display(),
+                to.display()
+            ));
+        }
+    }
+    (!collisions.is_empty()).then_some(collisions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    fn rename(template: &str, required: &[&str], files: Vec<PathBuf>) -> Rename {
+        Rename {
+            template: Template::parse(template).unwrap(),
+            options: Options::default(),
+            required: required.iter().map(|&field| field.into()).collect(),
+            dry_run: false,
+            undo_log: None,
+            files,
+        }
+    }
+
+    fn moved(from: &str, to: &str) -> Move {
+        Move {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let files = vec![
+            "shows/[Group] Title - 01 [1080p].mkv".into(),
+            "Title - 02.mkv".into(),
+            "Title.mkv".into(),
+        ];
+        let command = rename(
+            "{title} - {episode:02}.{file_extension}",
+            &["episode"],
+            files,
+        );
+        let mut err = Vec::new();
+        assert_eq!(
+            command.plan(&mut err).unwrap(),
+            [moved(
+                "shows/[Group] Title - 01 [1080p].mkv",
+                "shows/Title - 01.mkv"
+            )]
+        );
+        assert_eq!(
+            String::from_utf8(err).unwrap(),
+            "skipped 'Title.mkv': episode could not be found\n"
+        );
+
+        let command = rename("{title}/{episode}", &[], Vec::new());
+        let path = command.new_path(&Parser::new(), Path::new("Title - 01.mkv"));
+        assert!(path.is_err_and(|e| e.contains("path separator")));
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = std::env::temp_dir().join(format!("anitomy-rename-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "[A] Title - 01.mkv",
+            "[B] Title - 01.mkv",
+            "[A] It's - 02.mkv",
+        ] {
+            File::create(dir.join(name)).unwrap();
+        }
+        let mut command = rename(
+            "{title} - {episode:02}.{file_extension}",
+            &[],
+            vec![
+                dir.join("[A] Title - 01.mkv"),
+                dir.join("[B] Title - 01.mkv"),
+            ],
+        );
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        assert!(!command.run(&mut out, &mut err).unwrap());
+        assert!(out.is_empty());
+        assert!(dir.join("[A] Title - 01.mkv").exists());
+
+        command.files = vec![
+            dir.join("[A] Title - 01.mkv"),
+            dir.join("[A] It's - 02.mkv"),
+        ];
+        command.undo_log = Some(dir.join("undo.sh"));
+        assert!(command.run(&mut out, &mut err).unwrap());
+        assert!(dir.join("Title - 01.mkv").exists());
+        assert!(dir.join("It's - 02.mkv").exists());
+        let log = fs::read_to_string(dir.join("undo.sh")).unwrap();
+        assert!(log.ends_with(&format!(
+            "mv -n -- {} {}\n",
+            shell_quote(&dir.join("It's - 02.mkv")).unwrap(),
+            shell_quote(&dir.join("[A] It's - 02.mkv")).unwrap()
+        )));
+
+        // An existing undo log is never overwritten
+        command.files = vec![dir.join("[B] Title - 01.mkv")];
+        command.template = Template::parse("{title} - {episode:02}v2.{file_extension}").unwrap();
+        assert!(!command.run(&mut out, &mut err).unwrap());
+        assert!(dir.join("[B] Title - 01.mkv").exists());
+        assert_eq!(fs::read_to_string(dir.join("undo.sh")).unwrap(), log);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_collisions() {
+        let moves = [
+            moved("a - 01.mkv", "01.mkv"),
+            moved("b - 01.mkv", "01.mkv"),
+            moved("c - 02.mkv", "02.mkv"),
+            moved("d - 03.mkv", "03.mkv"),
+        ];
+        let collisions = find_collisions(&moves, |path| path == Path::new("02.mkv")).unwrap();
+        assert_eq!(
+            collisions,
+            [
+                "'a - 01.mkv' and 'b - 01.mkv' would both be renamed to '01.mkv'",
+                "'c - 02.mkv' would be renamed to '02.mkv', which already exists",
+            ]
+        );
+        assert_eq!(find_collisions(&moves[2..], |_| false), None);
+    }
+
+    #[test]
+    fn test_check_undo_log() {
+        let moves = [moved("a - 01.mkv", "undo.sh")];
+        let error = check_undo_log(Path::new("undo.sh"), &moves).unwrap();
+        assert!(error.is_some_and(|e| e.contains("would be renamed")));
+        let error = check_undo_log(Path::new("other.sh"), &moves).unwrap();
+        assert_eq!(error, None);
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let moves = [Move {
+                from: OsStr::from_bytes(b"\xff - 01.mkv").into(),
+                to: "01.mkv".into(),
+            }];
+            let error = check_undo_log(Path::new("other.sh"), &moves).unwrap();
+            assert!(error.is_some_and(|e| e.contains("not valid UTF-8")));
+        }
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(
+            shell_quote(Path::new("it's.mkv")).as_deref(),
+            Some(r"'it'\''s.mkv'")
+        );
+    }
+}
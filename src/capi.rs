@@ -0,0 +1,307 @@
+//! The C API, see `include/anitomy.h` for the generated header.
+
+use alloc::{boxed::Box, ffi::CString, vec::Vec};
+use core::{
+    ffi::{c_char, CStr},
+    ptr,
+};
+
+use crate::{
+    element::{Confidence, Element, ElementKind},
+    options::Options,
+    parser::Parser,
+};
+
+/// The options used when parsing.
+///
+/// Use `anitomy_options_default` to get the defaults, where everything is enabled.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnitomyOptions {
+    pub episode: bool,
+    pub episode_title: bool,
+    pub file_checksum: bool,
+    pub file_extension: bool,
+    pub release_group: bool,
+    pub season: bool,
+    pub title: bool,
+    pub video_resolution: bool,
+    pub year: bool,
+    pub date: bool,
+}
+
+impl From<AnitomyOptions> for Options {
+    fn from(value: AnitomyOptions) -> Self {
+        Self::default()
+            .episodes(value.episode)
+            .episode_titles(value.episode_title)
+            .file_checksums(value.file_checksum)
+            .file_extensions(value.file_extension)
+            .release_groups(value.release_group)
+            .seasons(value.season)
+            .titles(value.title)
+            .video_resolutions(value.video_resolution)
+            .years(value.year)
+            .dates(value.date)
+    }
+}
+
+/// A parsed element.
+///
+/// The value is owned by the `AnitomyElements` it came from and is valid until
+/// that is freed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct AnitomyElement {
+    pub kind: ElementKind,
+    pub confidence: Confidence,
+    /// The NUL-terminated UTF-8 value of the element
+    pub value: *const c_char,
+    /// The length of the value in bytes, without the NUL terminator
+    pub value_len: usize,
+    /// The byte offset in the input where the element starts
+    pub start: usize,
+    /// The byte offset in the input where the element ends
+    pub end: usize,
+}
+
+/// The elements of a parsed string.
+///
+/// This is created by `anitomy_parse` and must be freed with `anitomy_elements_free`.
+#[derive(Debug)]
+pub struct AnitomyElements {
+    elements: Vec<(AnitomyElement, CString)>,
+}
+
+impl AnitomyElements {
+    fn new(elements: Vec<Element<'_>>) -> Self {
+        let elements = elements
+            .into_iter()
+            .map(|element| {
+                // The input is NUL-terminated so the values can never contain a NUL
+                let value = CString::new(element.value.into_owned()).unwrap_or_default();
+                let raw = AnitomyElement {
+                    kind: element.kind,
+                    confidence: element.confidence,
+                    value: value.as_ptr(),
+                    value_len: value.as_bytes().len(),
+                    start: element.span.start,
+                    end: element.span.end,
+                };
+                (raw, value)
+            })
+            .collect();
+        Self { elements }
+    }
+}
+
+/// Returns the default options, where everything is enabled.
+#[no_mangle]
+pub extern "C" fn anitomy_options_default() -> AnitomyOptions {
+    AnitomyOptions {
+        episode: true,
+        episode_title: true,
+        file_checksum: true,
+        file_extension: true,
+        release_group: true,
+        season: true,
+        title: true,
+        video_resolution: true,
+        year: true,
+        date: true,
+    }
+}
+
+/// Parses a NUL-terminated UTF-8 string into its elements.
+///
+/// If `options` is `NULL` then the default options are used. Returns `NULL` if `input`
+/// is `NULL` or not valid UTF-8.
+///
+/// # Safety
+///
+/// `input` must be `NULL` or point to a NUL-terminated string, and `options` must be
+/// `NULL` or point to a valid `AnitomyOptions`.
+#[no_mangle]
+pub unsafe extern "C" fn anitomy_parse(
+    input: *const c_char,
+    options: *const AnitomyOptions,
+) -> *mut AnitomyElements {
+    if input.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return ptr::null_mut();
+    };
+    let options = options
+        .as_ref()
+        .copied()
+        .map(Options::from)
+        .unwrap_or_default();
+    let elements = Parser::new().options(options).parse(input);
+    Box::into_raw(Box::new(AnitomyElements::new(elements)))
+}
+
+/// Returns the number of parsed elements, or 0 if `elements` is `NULL`.
+///
+/// # Safety
+///
+/// `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn anitomy_elements_len(elements: *const AnitomyElements) -> usize {
+    elements
+        .as_ref()
+        .map(|e| e.elements.len())
+        .unwrap_or_default()
+}
+
+/// Writes the element at `index` to `out`, in the order they appear in the input.
+///
+/// Returns `false` if the index is out of bounds or any of the pointers are `NULL`,
+/// in which case `out` is left untouched.
+///
+/// # Safety
+///
+/// `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed,
+/// and `out` must be `NULL` or point to writable memory for an `AnitomyElement`.
+#[no_mangle]
+pub unsafe extern "C" fn anitomy_elements_get(
+    elements: *const AnitomyElements,
+    index: usize,
+    out: *mut AnitomyElement,
+) -> bool {
+    let Some(elements) = elements.as_ref() else {
+        return false;
+    };
+    match (elements.elements.get(index), out.is_null()) {
+        (Some((element, _)), false) => {
+            out.write(*element);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Frees the elements returned by `anitomy_parse`. Does nothing if `elements` is `NULL`.
+///
+/// # Safety
+///
+/// `elements` must be `NULL` or a pointer returned by `anitomy_parse` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn anitomy_elements_free(elements: *mut AnitomyElements) {
+    if !elements.is_null() {
+        drop(Box::from_raw(elements));
+    }
+}
+
+/// Returns the human readable name of the kind as a static NUL-terminated string,
+/// e.g. `Release Group`. Returns `NULL` if `kind` is not an `AnitomyElementKind`.
+#[no_mangle]
+pub extern "C" fn anitomy_element_kind_name(kind: u32) -> *const c_char {
+    // Indexed by the discriminants of `ElementKind`
+    static NAMES: [&CStr; 22] = [
+        c"Audio Term",
+        c"Device Compatibility",
+        c"Episode",
+        c"Episode Title",
+        c"Episode Alt",
+        c"File Checksum",
+        c"File Extension",
+        c"Language",
+        c"Other",
+        c"Release Group",
+        c"Release Information",
+        c"Release Version",
+        c"Season",
+        c"Source",
+        c"Subtitles",
+        c"Title",
+        c"Type",
+        c"Video Resolution",
+        c"Video Term",
+        c"Volume",
+        c"Year",
+        c"Date",
+    ];
+    NAMES
+        .get(kind as usize)
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn collect(elements: *const AnitomyElements) -> Vec<(ElementKind, &'static str)> {
+        (0..anitomy_elements_len(elements))
+            .map(|index| {
+                let mut element = core::mem::MaybeUninit::uninit();
+                assert!(anitomy_elements_get(elements, index, element.as_mut_ptr()));
+                let element = element.assume_init();
+                let value = CStr::from_ptr(element.value).to_str().unwrap();
+                assert_eq!(value.len(), element.value_len);
+                (element.kind, value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_capi() {
+        let input = c"[Group] Title - 01 [1080p].mkv";
+        let mut options = anitomy_options_default();
+        options.episode_title = false;
+        unsafe {
+            let elements = anitomy_parse(input.as_ptr(), &options);
+            assert!(!elements.is_null());
+            assert_eq!(
+                collect(elements),
+                [
+                    (ElementKind::ReleaseGroup, "Group"),
+                    (ElementKind::Title, "Title"),
+                    (ElementKind::Episode, "01"),
+                    (ElementKind::VideoResolution, "1080p"),
+                    (ElementKind::FileExtension, "mkv"),
+                ]
+            );
+
+            let mut element = core::mem::MaybeUninit::uninit();
+            assert!(!anitomy_elements_get(elements, 5, element.as_mut_ptr()));
+            assert!(!anitomy_elements_get(elements, 0, ptr::null_mut()));
+            anitomy_elements_free(elements);
+
+            assert!(anitomy_parse(ptr::null(), ptr::null()).is_null());
+            assert!(anitomy_parse(c"\xFF".as_ptr(), ptr::null()).is_null());
+            assert_eq!(anitomy_elements_len(ptr::null()), 0);
+            anitomy_elements_free(ptr::null_mut());
+        }
+
+        for kind in [
+            ElementKind::AudioTerm,
+            ElementKind::DeviceCompatibility,
+            ElementKind::Episode,
+            ElementKind::EpisodeTitle,
+            ElementKind::EpisodeAlt,
+            ElementKind::FileChecksum,
+            ElementKind::FileExtension,
+            ElementKind::Language,
+            ElementKind::Other,
+            ElementKind::ReleaseGroup,
+            ElementKind::ReleaseInformation,
+            ElementKind::ReleaseVersion,
+            ElementKind::Season,
+            ElementKind::Source,
+            ElementKind::Subtitles,
+            ElementKind::Title,
+            ElementKind::Type,
+            ElementKind::VideoResolution,
+            ElementKind::VideoTerm,
+            ElementKind::Volume,
+            ElementKind::Year,
+            ElementKind::Date,
+        ] {
+            let name = unsafe { CStr::from_ptr(anitomy_element_kind_name(kind as u32)) };
+            assert_eq!(name.to_str(), Ok(kind.as_str()));
+        }
+        assert!(anitomy_element_kind_name(22).is_null());
+        assert!(anitomy_element_kind_name(u32::MAX).is_null());
+    }
+}
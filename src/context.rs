@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+use core::{mem::ManuallyDrop, ops::Deref};
+
+use crate::{
+    element::Element,
+    parser::{parse_into, Parser},
+    tokenizer::Token,
+};
+
+/// Empties the buffer and reuses its allocation for another type.
+///
+/// # Safety
+///
+/// `T` and `U` must be the same type apart from their lifetimes.
+unsafe fn recycle<T, U>(mut buffer: Vec<T>) -> Vec<U> {
+    buffer.clear();
+    let mut buffer = ManuallyDrop::new(buffer);
+    // SAFETY: The buffer is empty so no value of the old type is left behind, and
+    // the caller guarantees that both types have the same size and alignment.
+    unsafe { Vec::from_raw_parts(buffer.as_mut_ptr().cast(), 0, buffer.capacity()) }
+}
+
+/// Empties the buffer of tokens and reuses its allocation for another lifetime
+fn recycle_tokens<'b>(buffer: Vec<Token<'_>>) -> Vec<Token<'b>> {
+    // SAFETY: Only the lifetime of the tokens changes
+    unsafe { recycle(buffer) }
+}
+
+/// Empties the buffer of elements and reuses its allocation for another lifetime
+fn recycle_elements<'b>(buffer: Vec<Element<'_>>) -> Vec<Element<'b>> {
+    // SAFETY: Only the lifetime of the elements changes
+    unsafe { recycle(buffer) }
+}
+
+/// A reusable context for parsing many strings with the same parser.
+///
+/// Every call to [`Parser::parse`] allocates the tokens and the elements from scratch.
+/// A context keeps these buffers around between calls so that parsing in a loop only
+/// allocates the values that have to be concatenated, such as titles.
+///
+/// ```
+/// use anitomy::{ElementKind, Parser};
+///
+/// let parser = Parser::new();
+/// let mut context = parser.context();
+/// for input in ["[Group] Title - 01.mkv", "[Group] Title - 02.mkv"] {
+///     let elements = context.parse(input);
+///     assert!(elements.iter().any(|e| e.kind() == ElementKind::Episode));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ParseContext<'p> {
+    parser: &'p Parser,
+    tokens: Vec<Token<'static>>,
+    elements: Vec<Element<'static>>,
+}
+
+impl<'p> ParseContext<'p> {
+    /// Creates a context that parses with the given parser
+    pub fn new(parser: &'p Parser) -> Self {
+        Self {
+            parser,
+            tokens: Vec::new(),
+            elements: Vec::new(),
+        }
+    }
+
+    /// Returns the parser used by this context
+    pub fn parser(&self) -> &'p Parser {
+        self.parser
+    }
+
+    /// Parses a string into its element components.
+    ///
+    /// The elements are the same as the ones returned by [`Parser::parse`]. They are
+    /// borrowed from the context until the returned value is dropped.
+    pub fn parse<'c, 'a>(&'c mut self, input: &'a str) -> Parsed<'c, 'a> {
+        let mut tokens = recycle_tokens(core::mem::take(&mut self.tokens));
+        let mut elements = recycle_elements(core::mem::take(&mut self.elements));
+        self.parser.tokenize_into(input, &mut tokens);
+        parse_into(&mut tokens, self.parser, &mut elements);
+        self.tokens = recycle_tokens(tokens);
+        Parsed {
+            elements,
+            buffer: &mut self.elements,
+        }
+    }
+}
+
+impl Parser {
+    /// Creates a reusable context for parsing many strings with this parser.
+    ///
+    /// See [`ParseContext`] for more information.
+    pub fn context(&self) -> ParseContext<'_> {
+        ParseContext::new(self)
+    }
+}
+
+/// The elements parsed by a [`ParseContext`].
+///
+/// This dereferences to a slice of the elements, sorted by position. The buffer
+/// is given back to the context when this is dropped.
+#[derive(Debug)]
+pub struct Parsed<'c, 'a> {
+    elements: Vec<Element<'a>>,
+    buffer: &'c mut Vec<Element<'static>>,
+}
+
+impl<'a> Deref for Parsed<'_, 'a> {
+    type Target = [Element<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.elements
+    }
+}
+
+impl Drop for Parsed<'_, '_> {
+    fn drop(&mut self) {
+        *self.buffer = recycle_elements(core::mem::take(&mut self.elements));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+    use alloc::borrow::ToOwned;
+
+    #[test]
+    fn test_parse_context() {
+        let inputs = [
+            "[TaigaSubs]_Toradora!_(2008)_-_01v2_-_Tiger_and_Dragon_[1280x720_H.264_FLAC][1234ABCD].mkv",
+            "[Group] Title S01E03 [1080p].mkv",
+            "Evangelion 2.22 Movie 3 - 05-02.mkv",
+            "",
+            "[Group] Title - 01.mkv",
+        ];
+        let parser = Parser::new().options(Options::default().episode_titles(false));
+        let mut context = parser.context();
+        for input in inputs {
+            let input = input.to_owned();
+            assert_eq!(&context.parse(&input)[..], &parser.parse(&input)[..]);
+        }
+
+        // The buffers are kept around once they're no longer borrowed
+        assert!(context.tokens.capacity() > 0);
+        assert!(context.elements.capacity() > 0);
+    }
+}
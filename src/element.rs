@@ -1,38 +1,48 @@
+#[cfg(feature = "node")]
+use napi_derive::napi;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
-use std::borrow::Cow;
+use alloc::{borrow::Cow, string::String};
+use core::ops::Range;
 
-use crate::tokenizer::Token;
+use crate::{
+    tokenizer::{tokens_span, Token},
+    utils::parse_number,
+};
 
-/// The kind of element that has been parsed
+/// The kind of element that has been parsed.
+///
+/// The discriminants are stable, new kinds are only ever added at the end.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[cfg_attr(feature = "node", napi)]
+#[repr(C)]
 pub enum ElementKind {
-    AudioTerm,
-    DeviceCompatibility,
-    Episode,
-    EpisodeTitle,
-    EpisodeAlt,
-    FileChecksum,
-    FileExtension,
-    Language,
-    Other,
-    ReleaseGroup,
-    ReleaseInformation,
-    ReleaseVersion,
-    Season,
-    Source,
-    Subtitles,
-    Title,
-    Type,
-    VideoResolution,
-    VideoTerm,
-    Volume,
-    Year,
-    Date,
+    AudioTerm = 0,
+    DeviceCompatibility = 1,
+    Episode = 2,
+    EpisodeTitle = 3,
+    EpisodeAlt = 4,
+    FileChecksum = 5,
+    FileExtension = 6,
+    Language = 7,
+    Other = 8,
+    ReleaseGroup = 9,
+    ReleaseInformation = 10,
+    ReleaseVersion = 11,
+    Season = 12,
+    Source = 13,
+    Subtitles = 14,
+    Title = 15,
+    Type = 16,
+    VideoResolution = 17,
+    VideoTerm = 18,
+    Volume = 19,
+    Year = 20,
+    Date = 21,
 }
 
 impl ElementKind {
@@ -65,6 +75,31 @@ impl ElementKind {
     }
 }
 
+/// How confident the parser is that an element is correct.
+///
+/// This is based on the rule that produced the element. For example, an episode
+/// found in `S01E03` has a `High` confidence while an episode that was found by
+/// falling back to the last number in the name has a `Low` confidence. Elements that
+/// come from an ambiguous keyword (e.g. `ESP`) have a lower confidence than those from
+/// an unambiguous one (e.g. `x264`).
+///
+/// Confidences are ordered, so `confidence >= Confidence::Medium` can be used to filter elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[cfg_attr(feature = "node", napi)]
+#[repr(C)]
+pub enum Confidence {
+    /// The element was found by a fallback heuristic and should be reviewed
+    Low = 0,
+    /// The element was found by a heuristic that is usually correct
+    Medium = 1,
+    /// The element was found by an unambiguous pattern or keyword
+    #[default]
+    High = 2,
+}
+
 /// A parsed element
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -75,6 +110,13 @@ pub struct Element<'a> {
     pub(crate) value: Cow<'a, str>,
     #[cfg_attr(feature = "serde", serde(default, skip))]
     pub(crate) position: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) span: Range<usize>,
+    /// Whether this element is the upper bound of a range (e.g. the `12` in `01-12`)
+    #[cfg_attr(feature = "serde", serde(default, skip))]
+    pub(crate) is_range_end: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) confidence: Confidence,
 }
 
 impl<'a> Element<'a> {
@@ -83,9 +125,49 @@ impl<'a> Element<'a> {
             kind,
             value: token.value.into(),
             position: token.position,
+            span: token.span(),
+            is_range_end: false,
+            confidence: Confidence::High,
+        }
+    }
+
+    /// Creates an element from a part of the token's value
+    pub(crate) fn from_part(kind: ElementKind, token: &Token<'a>, value: &'a str) -> Self {
+        Self {
+            kind,
+            value: value.into(),
+            position: token.position,
+            span: token.span_of(value),
+            is_range_end: false,
+            confidence: Confidence::High,
+        }
+    }
+
+    /// Creates an element that is made up of multiple tokens
+    pub(crate) fn from_tokens(kind: ElementKind, value: String, tokens: &[Token<'a>]) -> Self {
+        Self {
+            kind,
+            value: value.into(),
+            position: tokens.first().map(|t| t.position).unwrap_or_default(),
+            span: tokens_span(tokens),
+            is_range_end: false,
+            confidence: Confidence::High,
         }
     }
 
+    /// Marks this element as the upper bound of a range
+    pub(crate) fn ends_range(self) -> Self {
+        Self {
+            is_range_end: true,
+            ..self
+        }
+    }
+
+    /// Sets how confident the parser is in this element
+    pub(crate) fn with_confidence(self, confidence: Confidence) -> Self {
+        Self { confidence, ..self }
+    }
+
     /// Returns the kind of element
     pub fn kind(&self) -> ElementKind {
         self.kind
@@ -95,6 +177,51 @@ impl<'a> Element<'a> {
     pub fn value(&self) -> &str {
         &self.value
     }
+
+    /// Returns how confident the parser is that this element is correct
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// Returns the normalized numeric value of the element, if it has one.
+    ///
+    /// This is only available for number-bearing elements, i.e. [`ElementKind::Episode`],
+    /// [`ElementKind::EpisodeAlt`], [`ElementKind::Season`], [`ElementKind::Volume`],
+    /// [`ElementKind::ReleaseVersion`] and [`ElementKind::Year`]. Besides regular digits,
+    /// fullwidth digits (`１２`), kanji numerals (`十二`), roman numerals (`XII`) and
+    /// ordinals (`12th`, `Twelfth`) are also understood. Values that are not whole
+    /// numbers (e.g. `07.5`) return `None`.
+    ///
+    /// The raw value is still available through [`Element::value`].
+    pub fn number(&self) -> Option<u32> {
+        match self.kind {
+            ElementKind::Episode
+            | ElementKind::EpisodeAlt
+            | ElementKind::Season
+            | ElementKind::Volume
+            | ElementKind::ReleaseVersion
+            | ElementKind::Year => parse_number(&self.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte range in the original input where this element was found.
+    ///
+    /// For elements that are made up of multiple tokens (e.g. titles) this spans
+    /// from the first byte of the first token to the last byte of the last token.
+    /// Note that the value might not be equal to the input sliced by this range
+    /// since some values are normalised (e.g. `2nd Season` -> `2`).
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the span in UTF-16 code units, which is how JavaScript strings are indexed
+    #[cfg(any(feature = "wasm", feature = "node"))]
+    pub(crate) fn utf16_span(&self, input: &str) -> Range<usize> {
+        let start = input[..self.span.start].encode_utf16().count();
+        let end = start + input[self.span.clone()].encode_utf16().count();
+        start..end
+    }
 }
 
 /// A helper type that turns a slice of [`Element`] objects into a flat struct with multiple elements.
@@ -351,12 +478,12 @@ macro_rules! impl_from_iterator {
     ($($name:ident => $mapped:ident),+$(,)?) => {
         impl<'a, 'b: 'a> FromIterator<&'b Element<'a>> for ElementObject<'a> {
             fn from_iter<T: IntoIterator<Item = &'b Element<'a>>>(iter: T) -> Self {
-                use std::borrow::Borrow;
+                use core::borrow::Borrow;
                 let mut object = Self::default();
                 for element in iter {
                     match element.kind {
                         $(
-                            $crate::ElementKind::$name => object.$mapped = Some(std::borrow::Cow::Borrowed(element.value.borrow()))
+                            $crate::ElementKind::$name => object.$mapped = Some(alloc::borrow::Cow::Borrowed(element.value.borrow()))
                         ),+
                     }
                 }
@@ -433,19 +560,29 @@ impl_from_iterator! {
     Date => date,
 }
 
+/// A parsed element.
+///
+/// The span is in UTF-16 code units, so `input.slice(start, end)` works as expected.
 #[cfg(feature = "wasm")]
 #[cfg_attr(feature = "wasm", wasm_bindgen(getter_with_clone, js_name = Element))]
 pub struct JsElement {
     pub kind: ElementKind,
     pub value: String,
+    pub start: usize,
+    pub end: usize,
+    pub confidence: Confidence,
 }
 
 #[cfg(feature = "wasm")]
-impl<'a> From<Element<'a>> for JsElement {
-    fn from(el: Element<'a>) -> Self {
+impl JsElement {
+    pub(crate) fn new(input: &str, element: Element<'_>) -> Self {
+        let span = element.utf16_span(input);
         Self {
-            kind: el.kind,
-            value: el.value.into_owned(),
+            kind: element.kind,
+            value: element.value.into_owned(),
+            start: span.start,
+            end: span.end,
+            confidence: element.confidence,
         }
     }
 }
@@ -0,0 +1,315 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    element::{Element, ElementKind},
+    utils::{parse_number, parse_numerals},
+};
+
+/// The suffix of an episode number that denotes a part of an episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EpisodeSuffix {
+    /// The fractional part of an episode number, e.g. `5/10` for `07.5` or `5/100` for `07.05`
+    ///
+    /// The denominator is a power of ten with as many zeros as there are digits in the
+    /// fraction, so that leading and trailing zeros are kept.
+    Fraction {
+        /// The digits of the fraction, e.g. the `5` in `07.05`
+        numerator: u32,
+        /// The power of ten that the numerator is divided by, e.g. `100` for `07.05`
+        denominator: u32,
+    },
+    /// A partial episode marker, e.g. the `a` in `4a`
+    Part(char),
+}
+
+/// A single episode number, e.g. `01`, `07.5`, `4a` or `01v2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct EpisodeNumber {
+    /// The whole episode number
+    pub number: u16,
+    /// The fractional or partial suffix, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub suffix: Option<EpisodeSuffix>,
+    /// The release version of this specific episode, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub release_version: Option<u8>,
+}
+
+impl EpisodeNumber {
+    /// Creates an episode number with no suffix or release version
+    pub const fn new(number: u16) -> Self {
+        Self {
+            number,
+            suffix: None,
+            release_version: None,
+        }
+    }
+
+    /// Parses an episode number in the format of an [`ElementKind::Episode`] value.
+    ///
+    /// This is a whole number optionally followed by a fractional part (e.g. `07.5`)
+    /// or a partial episode marker (e.g. `4a`). Numbers written in kanji numerals
+    /// (e.g. `十二`) are also accepted, but roman numerals are not since too many words
+    /// look like one (e.g. `MIX` or `CD`). See [`EpisodeNumber::parse_with_roman`] for
+    /// values that are already known to be episodes.
+    pub fn parse(s: &str) -> Option<Self> {
+        if let Some(number) = parse_numerals(s) {
+            return Some(Self::new(number.try_into().ok()?));
+        }
+        let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, rest) = s.split_at(index);
+        let number = number.parse().ok()?;
+        let suffix = if rest.is_empty() {
+            None
+        } else if let Some(fraction) = rest.strip_prefix('.') {
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some(EpisodeSuffix::Fraction {
+                numerator: fraction.parse().ok()?,
+                denominator: 10u32.checked_pow(fraction.len().try_into().ok()?)?,
+            })
+        } else {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) if ch.is_ascii_alphabetic() => Some(EpisodeSuffix::Part(ch)),
+                _ => return None,
+            }
+        };
+        Some(Self {
+            number,
+            suffix,
+            release_version: None,
+        })
+    }
+
+    /// Parses an episode number like [`EpisodeNumber::parse`], also accepting roman
+    /// numerals (e.g. `XII`) and ordinal numbers (e.g. `12th`).
+    pub fn parse_with_roman(s: &str) -> Option<Self> {
+        Self::parse(s).or_else(|| Some(Self::new(parse_number(s)?.try_into().ok()?)))
+    }
+}
+
+/// A set of episodes that a release contains.
+///
+/// This is built from the [`ElementKind::Episode`] (or [`ElementKind::EpisodeAlt`]) elements
+/// of a single parse, e.g.
+///
+/// - `01` is a [`EpisodeSet::Single`] episode
+/// - `01-12` and `01~12` are a [`EpisodeSet::Range`] of episodes
+/// - `01+02`, `01&02` and `01, 03, 05` are a [`EpisodeSet::List`] of episodes
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EpisodeSet {
+    /// A single episode
+    Single(EpisodeNumber),
+    /// An inclusive range of episodes
+    Range {
+        start: EpisodeNumber,
+        end: EpisodeNumber,
+    },
+    /// A list of individual episodes
+    List(Vec<EpisodeNumber>),
+}
+
+impl EpisodeSet {
+    /// Builds the episode set for the given kind from a slice of elements.
+    ///
+    /// `kind` is either [`ElementKind::Episode`] or [`ElementKind::EpisodeAlt`].
+    /// Release versions that directly follow an episode are attached to it.
+    pub fn from_elements(elements: &[Element<'_>], kind: ElementKind) -> Option<Self> {
+        let mut set = None;
+        let mut previous = None;
+        for element in elements {
+            if element.kind == kind {
+                Self::push_value(&mut set, &element.value, element.is_range_end);
+            } else if element.kind == ElementKind::ReleaseVersion && previous == Some(kind) {
+                Self::set_release_version(&mut set, &element.value);
+            }
+            previous = Some(element.kind);
+        }
+        set
+    }
+
+    /// Adds an episode value to the set, ignoring values that are not valid episode numbers.
+    ///
+    /// A value after a range is added to the range's episodes, e.g. `01-03, 05` is a list
+    /// of `1`, `2`, `3` and `5`.
+    pub(crate) fn push_value(set: &mut Option<Self>, value: &str, is_range_end: bool) {
+        let Some(number) = EpisodeNumber::parse_with_roman(value) else {
+            return;
+        };
+        *set = Some(match set.take() {
+            None => Self::Single(number),
+            Some(Self::Single(start)) if is_range_end => Self::Range { start, end: number },
+            Some(Self::Single(first)) => Self::List(vec![first, number]),
+            Some(Self::Range { start, end }) => {
+                let middle = (start.number + 1..end.number).map(EpisodeNumber::new);
+                let mut list = core::iter::once(start)
+                    .chain(middle)
+                    .chain([end, number])
+                    .collect::<Vec<_>>();
+                list.dedup();
+                Self::List(list)
+            }
+            Some(Self::List(mut list)) => {
+                list.push(number);
+                Self::List(list)
+            }
+        });
+    }
+
+    /// Returns the first episode in the set
+    pub fn first(&self) -> &EpisodeNumber {
+        match self {
+            Self::Single(x) => x,
+            Self::Range { start, .. } => start,
+            Self::List(list) => &list[0],
+        }
+    }
+
+    /// Returns the last episode in the set
+    pub fn last(&self) -> &EpisodeNumber {
+        match self {
+            Self::Single(x) => x,
+            Self::Range { end, .. } => end,
+            Self::List(list) => &list[list.len() - 1],
+        }
+    }
+
+    /// Sets the release version of the last episode in the set
+    pub(crate) fn set_release_version(set: &mut Option<Self>, value: &str) {
+        if let Some(set) = set {
+            set.last_mut().release_version = value.parse().ok();
+        }
+    }
+
+    fn last_mut(&mut self) -> &mut EpisodeNumber {
+        match self {
+            Self::Single(x) => x,
+            Self::Range { end, .. } => end,
+            Self::List(list) => {
+                let index = list.len() - 1;
+                &mut list[index]
+            }
+        }
+    }
+
+    /// Returns a bool indicating whether the whole episode number is part of this set
+    pub fn contains(&self, number: u16) -> bool {
+        match self {
+            Self::Single(x) => x.number == number,
+            Self::Range { start, end } => (start.number..=end.number).contains(&number),
+            Self::List(list) => list.iter().any(|x| x.number == number),
+        }
+    }
+
+    /// Returns an iterator over every whole episode number in this set.
+    ///
+    /// Ranges are expanded, e.g. `01-03` yields `1`, `2` and `3`.
+    pub fn numbers(&self) -> impl Iterator<Item = u16> + '_ {
+        let (range, list) = match self {
+            Self::Single(x) => (Some(x.number..=x.number), &[][..]),
+            Self::Range { start, end } => (Some(start.number..=end.number), &[][..]),
+            Self::List(list) => (None, &list[..]),
+        };
+        range
+            .into_iter()
+            .flatten()
+            .chain(list.iter().map(|x| x.number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episodes(input: &str) -> Option<EpisodeSet> {
+        EpisodeSet::from_elements(&crate::parse(input), ElementKind::Episode)
+    }
+
+    #[test]
+    fn test_episode_number() {
+        assert_eq!(EpisodeNumber::parse("01"), Some(EpisodeNumber::new(1)));
+        assert_eq!(
+            EpisodeNumber::parse("07.5").and_then(|x| x.suffix),
+            Some(EpisodeSuffix::Fraction {
+                numerator: 5,
+                denominator: 10
+            })
+        );
+        assert_eq!(
+            EpisodeNumber::parse("07.05").and_then(|x| x.suffix),
+            Some(EpisodeSuffix::Fraction {
+                numerator: 5,
+                denominator: 100
+            })
+        );
+        assert_ne!(EpisodeNumber::parse("07.5"), EpisodeNumber::parse("07.05"));
+        assert_eq!(
+            EpisodeNumber::parse("07.500").and_then(|x| x.suffix),
+            Some(EpisodeSuffix::Fraction {
+                numerator: 500,
+                denominator: 1000
+            })
+        );
+        assert_eq!(EpisodeNumber::parse("07.5000000000"), None);
+        assert_eq!(
+            EpisodeNumber::parse("4a").and_then(|x| x.suffix),
+            Some(EpisodeSuffix::Part('a'))
+        );
+        assert_eq!(EpisodeNumber::parse("4ab"), None);
+        assert_eq!(EpisodeNumber::parse("v2"), None);
+        assert_eq!(EpisodeNumber::parse("十二"), Some(EpisodeNumber::new(12)));
+        assert_eq!(EpisodeNumber::parse("MIX"), None);
+        assert_eq!(EpisodeNumber::parse("CD"), None);
+        assert_eq!(
+            EpisodeNumber::parse_with_roman("XII"),
+            Some(EpisodeNumber::new(12))
+        );
+    }
+
+    #[test]
+    fn test_episode_sets() {
+        let range = episodes("[Group] Title - 01-12 [720p]").unwrap();
+        assert_eq!(
+            range,
+            EpisodeSet::Range {
+                start: EpisodeNumber::new(1),
+                end: EpisodeNumber::new(12),
+            }
+        );
+        assert_eq!(range.numbers().count(), 12);
+
+        let list = episodes("[Group] Title - 01+02 [720p]").unwrap();
+        assert_eq!(
+            list,
+            EpisodeSet::List(vec![EpisodeNumber::new(1), EpisodeNumber::new(2)])
+        );
+
+        let list = episodes("Title - 01, 03, 05 [720p]").unwrap();
+        assert_eq!(list.numbers().collect::<Vec<_>>(), [1, 3, 5]);
+
+        let versioned = episodes("Title #02-03v2").unwrap();
+        assert_eq!(versioned.first().release_version, None);
+        assert_eq!(versioned.last().release_version, Some(2));
+        assert!(versioned.contains(2) && versioned.contains(3));
+
+        // A value after a range keeps every episode of the range
+        let mut set = None;
+        for (value, is_range_end) in [("01", false), ("03", true), ("05", false)] {
+            EpisodeSet::push_value(&mut set, value, is_range_end);
+        }
+        assert_eq!(set.unwrap().numbers().collect::<Vec<_>>(), [1, 2, 3, 5]);
+    }
+}
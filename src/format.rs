@@ -0,0 +1,482 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    element::{ElementObject, OwnedElementObject},
+    episode::{EpisodeNumber, EpisodeSet, EpisodeSuffix},
+    release::{NumberRange, ParsedRelease},
+};
+
+/// The names of the fields that can be used in a template.
+///
+/// These are the same as the serialized names of the [`ElementObject`] fields.
+pub(crate) const FIELDS: [&str; 22] = [
+    "audio_term",
+    "device_compatibility",
+    "episode",
+    "episode_alt",
+    "episode_title",
+    "file_checksum",
+    "file_extension",
+    "language",
+    "other",
+    "release_group",
+    "release_information",
+    "release_version",
+    "season",
+    "source",
+    "subtitles",
+    "title",
+    "type",
+    "video_resolution",
+    "video_term",
+    "volume",
+    "year",
+    "date",
+];
+
+/// An error that occurred while parsing a [`Template`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{` at the given byte offset was never closed
+    UnclosedBrace(usize),
+    /// A `}` at the given byte offset has no matching `{`
+    UnmatchedBrace(usize),
+    /// The field name is not known
+    UnknownField(String),
+    /// The format spec of a field is not valid
+    InvalidSpec(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedBrace(offset) => write!(f, "unclosed `{{` at offset {offset}"),
+            Self::UnmatchedBrace(offset) => write!(f, "unmatched `}}` at offset {offset}"),
+            Self::UnknownField(name) => write!(f, "unknown field `{name}`"),
+            Self::InvalidSpec(spec) => write!(f, "invalid format spec `{spec}`"),
+        }
+    }
+}
+
+impl core::error::Error for TemplateError {}
+
+/// A type that provides the values of the fields used by a [`Template`].
+///
+/// This is implemented for [`ElementObject`], [`OwnedElementObject`] and [`ParsedRelease`].
+pub trait TemplateFields {
+    /// Returns the value of the field with the given name, if it's present.
+    ///
+    /// The name is one of the serialized names of the [`ElementObject`] fields, e.g. `title`
+    /// or `release_group`.
+    fn field(&self, name: &str) -> Option<Cow<'_, str>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field {
+        name: &'static str,
+        width: usize,
+    },
+    Conditional {
+        name: &'static str,
+        present: bool,
+        parts: Vec<Part>,
+    },
+}
+
+/// A template that renders parsed elements back into a name.
+///
+/// Templates are made up of text and fields in braces, using the same names as the
+/// serialized [`ElementObject`] fields (e.g. `{title}` or `{release_group}`). The
+/// following syntax is supported:
+///
+/// - `{field}` renders the value of the field, or nothing if it's missing.
+/// - `{field:0N}` renders the value with every number zero padded to at least `N` digits,
+///   e.g. `{episode:02}` renders `1` as `01` and `1-12` as `01-12`.
+/// - `{?field:...}` renders the inner template only if the field is present.
+/// - `{!field:...}` renders the inner template only if the field is missing.
+/// - `{{` and `}}` render a literal `{` and `}`.
+///
+/// Unknown field names are rejected when the template is parsed.
+///
+/// ```
+/// use anitomy::{ParsedRelease, Template};
+///
+/// let template = Template::parse(
+///     "[{release_group}] {title} - {episode:02}{?release_version:v{release_version}} [{video_resolution}].{file_extension}",
+/// )
+/// .unwrap();
+///
+/// let elements = anitomy::parse("[TaigaSubs]_Toradora!_-_1v2_[1280x720].mkv");
+/// let release = elements.iter().collect::<ParsedRelease>();
+/// assert_eq!(
+///     template.render(&release),
+///     "[TaigaSubs] Toradora! - 01v2 [1280x720].mkv"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    /// Parses a template
+    pub fn parse(s: &str) -> Result<Self, TemplateError> {
+        let mut parser = TemplateParser {
+            input: s,
+            offset: 0,
+        };
+        let parts = parser.parse_parts(None)?;
+        Ok(Self { parts })
+    }
+
+    /// Renders the template with the values of the given fields
+    pub fn render<T: TemplateFields + ?Sized>(&self, fields: &T) -> String {
+        let mut output = String::new();
+        render_parts(&self.parts, fields, &mut output);
+        output
+    }
+}
+
+impl FromStr for Template {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn render_parts<T: TemplateFields + ?Sized>(parts: &[Part], fields: &T, output: &mut String) {
+    for part in parts {
+        match part {
+            Part::Text(text) => output.push_str(text),
+            Part::Field { name, width } => {
+                if let Some(value) = fields.field(name) {
+                    pad_numbers(&value, *width, output);
+                }
+            }
+            Part::Conditional {
+                name,
+                present,
+                parts,
+            } => {
+                if fields.field(name).is_some() == *present {
+                    render_parts(parts, fields, output);
+                }
+            }
+        }
+    }
+}
+
+/// Zero pads every whole number in the value to at least `width` digits.
+///
+/// Digits after a `.` are a fractional part and are left as-is, e.g. `7.5` becomes `07.5`.
+fn pad_numbers(value: &str, width: usize, output: &mut String) {
+    let mut rest = value;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let end = rest[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |index| start + index);
+        let (before, digits) = (&rest[..start], &rest[start..end]);
+        output.push_str(before);
+        if !before.ends_with('.') {
+            output.extend(core::iter::repeat_n(
+                '0',
+                width.saturating_sub(digits.len()),
+            ));
+        }
+        output.push_str(digits);
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+}
+
+struct TemplateParser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> TemplateParser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.offset += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Parses parts until the end of the input or, if `opened` is the offset of an
+    /// unclosed `{`, until its matching `}`
+    fn parse_parts(&mut self, opened: Option<usize>) -> Result<Vec<Part>, TemplateError> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        loop {
+            let offset = self.offset;
+            match self.next() {
+                None => match opened {
+                    Some(opened) => return Err(TemplateError::UnclosedBrace(opened)),
+                    None => break,
+                },
+                Some('{') if self.peek() == Some('{') => {
+                    self.next();
+                    text.push('{');
+                }
+                Some('}') if self.peek() == Some('}') => {
+                    self.next();
+                    text.push('}');
+                }
+                Some('}') => match opened {
+                    Some(_) => break,
+                    None => return Err(TemplateError::UnmatchedBrace(offset)),
+                },
+                Some('{') => {
+                    if !text.is_empty() {
+                        parts.push(Part::Text(core::mem::take(&mut text)));
+                    }
+                    parts.push(self.parse_placeholder(offset)?);
+                }
+                Some(ch) => text.push(ch),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Ok(parts)
+    }
+
+    /// Parses a placeholder after its opening `{` at the given offset
+    fn parse_placeholder(&mut self, opened: usize) -> Result<Part, TemplateError> {
+        let rest = &self.input[self.offset..];
+        let end = rest
+            .find([':', '}'])
+            .ok_or(TemplateError::UnclosedBrace(opened))?;
+        let head = &rest[..end];
+        let delimiter = rest[end..].chars().next();
+        self.offset += end + 1;
+
+        let (present, name) = match head.chars().next() {
+            Some('?') => (Some(true), &head[1..]),
+            Some('!') => (Some(false), &head[1..]),
+            _ => (None, head),
+        };
+        let name = FIELDS
+            .into_iter()
+            .find(|field| *field == name)
+            .ok_or_else(|| TemplateError::UnknownField(name.to_owned()))?;
+
+        match (present, delimiter) {
+            (Some(present), Some(':')) => Ok(Part::Conditional {
+                name,
+                present,
+                parts: self.parse_parts(Some(opened))?,
+            }),
+            (Some(_), _) => Err(TemplateError::InvalidSpec(head.to_owned())),
+            (None, Some(':')) => {
+                let rest = &self.input[self.offset..];
+                let end = rest.find('}').ok_or(TemplateError::UnclosedBrace(opened))?;
+                let spec = &rest[..end];
+                self.offset += end + 1;
+                let width = spec
+                    .strip_prefix('0')
+                    .and_then(|width| width.parse().ok())
+                    .ok_or_else(|| TemplateError::InvalidSpec(spec.to_owned()))?;
+                Ok(Part::Field { name, width })
+            }
+            (None, _) => Ok(Part::Field { name, width: 0 }),
+        }
+    }
+}
+
+macro_rules! impl_template_fields {
+    ($($ty:ty),+$(,)?) => {
+        $(
+            impl TemplateFields for $ty {
+                fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+                    let value = match name {
+                        "audio_term" => &self.audio_term,
+                        "device_compatibility" => &self.device_compatibility,
+                        "episode" => &self.episode,
+                        "episode_alt" => &self.episode_alt,
+                        "episode_title" => &self.episode_title,
+                        "file_checksum" => &self.file_checksum,
+                        "file_extension" => &self.file_extension,
+                        "language" => &self.language,
+                        "other" => &self.other,
+                        "release_group" => &self.release_group,
+                        "release_information" => &self.release_information,
+                        "release_version" => &self.release_version,
+                        "season" => &self.season,
+                        "source" => &self.source,
+                        "subtitles" => &self.subtitles,
+                        "title" => &self.title,
+                        "type" => &self.kind,
+                        "video_resolution" => &self.video_resolution,
+                        "video_term" => &self.video_term,
+                        "volume" => &self.volume,
+                        "year" => &self.year,
+                        "date" => &self.date,
+                        _ => return None,
+                    };
+                    value.as_deref().map(Cow::Borrowed)
+                }
+            }
+        )+
+    };
+}
+
+impl_template_fields!(ElementObject<'_>, OwnedElementObject);
+
+fn join(values: &[Cow<'_, str>]) -> Option<Cow<'static, str>> {
+    (!values.is_empty()).then(|| values.join(" ").into())
+}
+
+fn episode_to_string(episode: &EpisodeNumber) -> String {
+    match episode.suffix {
+        None => episode.number.to_string(),
+        Some(EpisodeSuffix::Fraction {
+            numerator,
+            denominator,
+        }) => {
+            let width = denominator.ilog10() as usize;
+            format!("{}.{numerator:0width$}", episode.number)
+        }
+        Some(EpisodeSuffix::Part(part)) => format!("{}{part}", episode.number),
+    }
+}
+
+fn episodes_to_string(episodes: &EpisodeSet) -> String {
+    match episodes {
+        EpisodeSet::Single(x) => episode_to_string(x),
+        EpisodeSet::Range { start, end } => {
+            format!("{}-{}", episode_to_string(start), episode_to_string(end))
+        }
+        EpisodeSet::List(list) => list
+            .iter()
+            .map(episode_to_string)
+            .collect::<Vec<_>>()
+            .join("+"),
+    }
+}
+
+fn range_to_string(range: &NumberRange) -> String {
+    match range {
+        NumberRange::Single(x) => x.to_string(),
+        NumberRange::Range { start, end } => format!("{start}-{end}"),
+    }
+}
+
+/// Numbers are rendered without padding and multiple values are separated by spaces.
+/// Episode ranges are rendered as `1-12` and lists as `1+3`. Checksums are rendered as
+/// 8 uppercase hexadecimal digits and dates in `YYYY-MM-DD` format.
+impl TemplateFields for ParsedRelease<'_> {
+    fn field(&self, name: &str) -> Option<Cow<'_, str>> {
+        let owned = |value: Option<String>| value.map(Cow::Owned);
+        match name {
+            "audio_term" => join(&self.audio_term),
+            "device_compatibility" => join(&self.device_compatibility),
+            "episode" => owned(self.episode.as_ref().map(episodes_to_string)),
+            "episode_alt" => owned(self.episode_alt.as_ref().map(episodes_to_string)),
+            "episode_title" => self.episode_title.as_deref().map(Cow::Borrowed),
+            "file_checksum" => owned(self.file_checksum.map(|x| format!("{x:08X}"))),
+            "file_extension" => self.file_extension.as_deref().map(Cow::Borrowed),
+            "language" => join(&self.language),
+            "other" => join(&self.other),
+            "release_group" => self.release_group.as_deref().map(Cow::Borrowed),
+            "release_information" => join(&self.release_information),
+            "release_version" => owned(self.release_version.map(|x| x.to_string())),
+            "season" => owned(self.season.as_ref().map(range_to_string)),
+            "source" => join(&self.source),
+            "subtitles" => join(&self.subtitles),
+            "title" => self.title.as_deref().map(Cow::Borrowed),
+            "type" => self.kind.as_deref().map(Cow::Borrowed),
+            "video_resolution" => owned(self.video_resolution.map(|x| match x.width {
+                Some(width) => format!("{width}x{}", x.height),
+                None => format!("{}p", x.height),
+            })),
+            "video_term" => join(&self.video_term),
+            "volume" => owned(self.volume.as_ref().map(range_to_string)),
+            "year" => owned(self.year.map(|x| x.to_string())),
+            "date" => owned(
+                self.date
+                    .map(|x| format!("{:04}-{:02}-{:02}", x.year, x.month, x.day)),
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_parse() {
+        assert!(Template::parse("{title} - {episode:02}").is_ok());
+        assert!(Template::parse("{{literal}} {?year:({year})}{!year:(unknown)}").is_ok());
+        assert_eq!(
+            Template::parse("{title"),
+            Err(TemplateError::UnclosedBrace(0))
+        );
+        assert_eq!(
+            Template::parse("{?year:({year})"),
+            Err(TemplateError::UnclosedBrace(0))
+        );
+        assert_eq!(
+            Template::parse("{title} }"),
+            Err(TemplateError::UnmatchedBrace(8))
+        );
+        assert_eq!(
+            Template::parse("{name}"),
+            Err(TemplateError::UnknownField(String::from("name")))
+        );
+        assert_eq!(
+            Template::parse("{episode:2}"),
+            Err(TemplateError::InvalidSpec(String::from("2")))
+        );
+        assert_eq!(
+            Template::parse("{?episode}"),
+            Err(TemplateError::InvalidSpec(String::from("?episode")))
+        );
+    }
+
+    #[test]
+    fn test_template_render() {
+        let elements = crate::parse(
+            "[Group] Title S01-S02 - 7.5 (2012.05.06) [1920x1080 AAC FLAC][ABCD1234].mkv",
+        );
+        let object = elements.iter().collect::<ElementObject>();
+        let owned = elements.iter().collect::<OwnedElementObject>();
+        let release = elements.iter().collect::<ParsedRelease>();
+
+        let template = Template::parse(
+            "{title} S{season:02}E{episode:02}{?release_version:v{release_version}}{!year: {{{date}}}}",
+        )
+        .unwrap();
+        assert_eq!(template.render(&object), "Title S02E07.5 {2012.05.06}");
+        assert_eq!(template.render(&owned), "Title S02E07.5 {2012.05.06}");
+        assert_eq!(template.render(&release), "Title S01-02E07.5 {2012-05-06}");
+
+        let template = Template::parse("{audio_term} [{file_checksum}]").unwrap();
+        assert_eq!(template.render(&object), "FLAC [ABCD1234]");
+        assert_eq!(template.render(&release), "AAC FLAC [ABCD1234]");
+
+        let elements = crate::parse("Title - 01+03 [720p].mkv");
+        let release = elements.iter().collect::<ParsedRelease>();
+        let template = "{title} - {episode:02} [{video_resolution}]".parse::<Template>();
+        assert_eq!(template.unwrap().render(&release), "Title - 01+03 [720p]");
+
+        let episode = |s| episode_to_string(&EpisodeNumber::parse(s).unwrap());
+        assert_eq!(episode("07.5"), "7.5");
+        assert_eq!(episode("07.05"), "7.05");
+        assert_eq!(episode("07.500"), "7.500");
+    }
+}
@@ -0,0 +1,297 @@
+use alloc::{borrow::ToOwned, collections::BTreeMap};
+use core::ops::Range;
+
+use uncased::{Uncased, UncasedStr};
+
+use crate::tokenizer::Token;
+
+/// Release groups that are known by default.
+///
+/// These are usually placed at the end of the name (e.g. `Title_[1080p]_-_THORA.mkv`)
+/// where they can't be told apart from the rest of the name otherwise.
+const BUILTIN_RELEASE_GROUPS: [&str; 3] = ["JPTVclub", "THORA", "UTW-THORA"];
+
+/// A dictionary of known release groups.
+///
+/// Known release groups are found before the title is parsed, so groups placed at the
+/// end of the name (e.g. `Title - 01 [720p]-GROUP.mkv`) are detected reliably. Anywhere
+/// else, a group has to take up an entire bracket. Names are matched case insensitively
+/// and can contain delimiters (e.g. `UTW-THORA`).
+///
+/// Collaborations between known groups that are joined by `&`, `+`, `-` or `x`
+/// (e.g. `GroupA&GroupB`) are detected as a single release group even if the
+/// combination itself is not in the dictionary.
+///
+/// ```
+/// use anitomy::{ElementKind, Parser, ReleaseGroupTable};
+///
+/// let mut groups = ReleaseGroupTable::new();
+/// groups.insert("Foo");
+/// groups.insert("Bar");
+///
+/// let parser = Parser::new().release_groups(groups);
+/// let elements = parser.parse("Title - 01 [1080p]-Foo&Bar.mkv");
+/// assert!(elements
+///     .iter()
+///     .any(|e| e.kind() == ElementKind::ReleaseGroup && e.value() == "Foo&Bar"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReleaseGroupTable {
+    /// Overrides of the built-in groups, a `false` value means the group is disabled
+    entries: BTreeMap<Uncased<'static>, bool>,
+    builtin: bool,
+}
+
+impl Default for ReleaseGroupTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReleaseGroupTable {
+    /// Creates a dictionary that only contains the built-in release groups
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            builtin: true,
+        }
+    }
+
+    /// Creates a dictionary without any of the built-in release groups
+    pub const fn empty() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            builtin: false,
+        }
+    }
+
+    /// Adds a release group to the dictionary
+    pub fn insert(&mut self, name: &str) {
+        self.entries.insert(Uncased::new(name.to_owned()), true);
+    }
+
+    /// Disables a release group, including built-in ones
+    pub fn disable(&mut self, name: &str) {
+        self.entries.insert(Uncased::new(name.to_owned()), false);
+    }
+
+    /// Removes any added or disabled entry for the release group.
+    ///
+    /// If it's a built-in release group then it's known again.
+    pub fn reset(&mut self, name: &str) {
+        self.entries.remove(UncasedStr::new(name));
+    }
+
+    /// Returns a bool indicating whether the release group is in the dictionary
+    pub fn contains(&self, name: &str) -> bool {
+        let key = UncasedStr::new(name);
+        match self.entries.get(key) {
+            Some(enabled) => *enabled,
+            None => {
+                self.builtin
+                    && BUILTIN_RELEASE_GROUPS
+                        .iter()
+                        .any(|x| key == UncasedStr::new(x))
+            }
+        }
+    }
+
+    /// Returns an iterator over every release group in the dictionary, in no particular order.
+    ///
+    /// Disabled release groups are not included.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        let builtin = BUILTIN_RELEASE_GROUPS
+            .iter()
+            .copied()
+            .filter(|name| self.builtin && !self.entries.contains_key(UncasedStr::new(name)));
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|(name, enabled)| enabled.then_some(name.as_str()));
+        builtin.chain(entries)
+    }
+
+    /// Returns the number of tokens starting at `index` that make up a known release group
+    fn match_at(&self, tokens: &[Token<'_>], index: usize) -> Option<usize> {
+        if !tokens.get(index)?.is_free() {
+            return None;
+        }
+
+        // Prefer the longest name, e.g. `UTW-THORA` over `UTW`
+        self.iter()
+            .filter_map(|name| Self::match_name(tokens, index, name))
+            .max()
+    }
+
+    /// Returns the number of tokens starting at `index` that spell out `name`
+    fn match_name(tokens: &[Token<'_>], index: usize, name: &str) -> Option<usize> {
+        let mut rest = name;
+        for (count, token) in tokens[index..].iter().enumerate() {
+            if !(token.is_free() || token.is_delimiter()) {
+                return None;
+            }
+            let prefix = rest.get(..token.value.len())?;
+            if !prefix.eq_ignore_ascii_case(token.value) {
+                return None;
+            }
+            rest = &rest[token.value.len()..];
+            if rest.is_empty() {
+                return token.is_not_delimiter().then_some(count + 1);
+            }
+        }
+        None
+    }
+
+    /// Returns the number of tokens starting at `index` that separate two groups in a collaboration
+    fn match_separator(tokens: &[Token<'_>], index: usize) -> Option<usize> {
+        let token = tokens.get(index)?;
+        if token.is_delimiter() && token.value.starts_with(['&', '+', '-']) {
+            return Some(1);
+        }
+        // e.g. `GroupA x GroupB`
+        match tokens.get(index..index + 3)? {
+            [before, middle, after]
+                if before.is_delimiter()
+                    && after.is_delimiter()
+                    && (middle.value.eq_ignore_ascii_case("x") || middle.value == "×") =>
+            {
+                Some(3)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the end of the known release group starting at `index`, including collaborations
+    fn match_group(&self, tokens: &[Token<'_>], index: usize) -> Option<usize> {
+        let mut end = index + self.match_at(tokens, index)?;
+        while let Some(separator) = Self::match_separator(tokens, end) {
+            match self.match_at(tokens, end + separator) {
+                Some(length) => end += separator + length,
+                None => break,
+            }
+        }
+        Some(end)
+    }
+
+    /// Finds the range of tokens of a known release group, including collaborations.
+    ///
+    /// Groups either take up an entire bracket (e.g. `[GROUP]` but not `[GROUP-Encodes]`)
+    /// or come last in the name, as the last of the unenclosed text (e.g. `Title 01 GROUP.mkv`)
+    /// or after a dash or a bracket (e.g. `Title - 01 [720p]-GROUP.mkv`).
+    /// A known group anywhere else is probably a part of the title.
+    pub(crate) fn find(&self, tokens: &[Token<'_>]) -> Option<Range<usize>> {
+        for (index, token) in tokens.iter().enumerate() {
+            if !token.is_open_bracket() {
+                continue;
+            }
+            let start = index + 1;
+            if let Some(end) = self.match_group(tokens, start) {
+                if tokens.get(end).is_some_and(|t| t.is_closed_bracket()) {
+                    return Some(start..end);
+                }
+            }
+        }
+
+        let is_text = |t: &Token<'_>| t.is_free() && t.is_not_delimiter() && !t.is_enclosed;
+        let first = tokens.iter().position(is_text)?;
+        let last = tokens.iter().rposition(is_text)?;
+        // The group can't be all of the unenclosed text since that's the title
+        (first + 1..tokens.len()).find_map(|start| {
+            let end = self.match_group(tokens, start)?;
+            // The last of the text before the extension (e.g. `Title 01 GROUP.mkv`)
+            if end == last + 1 {
+                return Some(start..end);
+            }
+
+            let delimiters = tokens[..start]
+                .iter()
+                .rev()
+                .take_while(|t| t.is_delimiter())
+                .count();
+            let is_after_separator = tokens[start - delimiters..start]
+                .iter()
+                .any(|t| t.value == "-")
+                || tokens[..start - delimiters]
+                    .last()
+                    .is_some_and(|t| t.is_closed_bracket());
+            // Nothing else can be separated from the title after the group
+            let is_last = tokens[end..]
+                .iter()
+                .all(|t| !t.is_open_bracket() && t.value != "-");
+            (is_after_separator && is_last).then_some(start..end)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ElementKind, Parser};
+    use alloc::string::String;
+
+    fn release_group(parser: &Parser, input: &str) -> Option<String> {
+        parser
+            .parse(input)
+            .into_iter()
+            .find(|e| e.kind() == ElementKind::ReleaseGroup)
+            .map(|e| e.value().to_owned())
+    }
+
+    #[test]
+    fn test_release_group_table() {
+        let mut groups = ReleaseGroupTable::new();
+        assert!(groups.contains("thora"));
+        groups.disable("THORA");
+        groups.insert("Foo");
+        assert!(!groups.contains("THORA"));
+        assert!(groups.contains("foo"));
+        assert!(groups.iter().any(|x| x == "UTW-THORA"));
+        groups.reset("THORA");
+        assert!(groups.contains("THORA"));
+        assert!(!ReleaseGroupTable::empty().contains("THORA"));
+    }
+
+    #[test]
+    fn test_known_release_groups() {
+        let mut parser = Parser::new();
+        let groups = parser.release_groups_mut();
+        groups.insert("Foo");
+        groups.insert("Bar Subs");
+
+        let cases = [
+            ("Title - 01 [1080p]-Foo.mkv", Some("Foo")),
+            ("Title - 01 [1080p]-Foo&Bar Subs.mkv", Some("Foo&Bar Subs")),
+            (
+                "Title - 01 [1080p] Foo x Bar Subs.mkv",
+                Some("Foo x Bar Subs"),
+            ),
+            ("[Foo] Title - 01 [1080p].mkv", Some("Foo")),
+            ("[Foo-Encodes] Title - 01 [1080p].mkv", Some("Foo-Encodes")),
+            ("Title - 01 [1080p]-Other.mkv", Some("Other")),
+            // Known groups that are a part of the title are ignored
+            ("[Other] Foo - 01 [1080p].mkv", Some("Other")),
+            ("Title Foo - 01 [1080p].mkv", None),
+            // Built-in groups at the end of the name
+            ("Title 01 JPTVclub.mkv", Some("JPTVclub")),
+            ("Title_-_01_[720p]_-_THORA.mkv", Some("THORA")),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                release_group(&parser, input).as_deref(),
+                expected,
+                "{input}"
+            );
+        }
+
+        let mut parser = Parser::new();
+        parser.release_groups_mut().insert("Chihiro");
+        let input = "[SubsPlease] Sen to Chihiro no Kamikakushi (1080p).mkv";
+        assert_eq!(release_group(&parser, input).as_deref(), Some("SubsPlease"));
+        let elements = parser.parse(input);
+        let title = elements.iter().find(|e| e.kind() == ElementKind::Title);
+        assert_eq!(
+            title.map(|e| e.value()),
+            Some("Sen to Chihiro no Kamikakushi")
+        );
+    }
+}
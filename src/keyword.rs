@@ -1,8 +1,14 @@
+use alloc::{borrow::ToOwned, collections::BTreeMap};
+use core::ops::Bound;
+
 use phf::phf_map;
-use uncased::UncasedStr;
+use uncased::{Uncased, UncasedStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) enum KeywordKind {
+/// The category of a keyword
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum KeywordKind {
     AudioChannels,
     AudioCodec,
     AudioLanguage,
@@ -29,8 +35,15 @@ pub(crate) enum KeywordKind {
     Volume,
 }
 
+/// A keyword that the tokenizer recognises.
+///
+/// By default a keyword is unambiguous and bounded:
+///
+/// - An *ambiguous* keyword might also be a part of the title (e.g. `ESP` in `Tokyo ESP`),
+///   so it's only claimed as metadata when it's enclosed in brackets.
+/// - An *unbounded* keyword can be directly followed by other text (e.g. `1080p` in `1080pHEVC`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct Keyword {
+pub struct Keyword {
     pub(crate) kind: KeywordKind,
     flags: u8,
 }
@@ -39,28 +52,61 @@ impl Keyword {
     const AMBIGUOUS: u8 = 1 << 0;
     const UNBOUNDED: u8 = 1 << 1;
 
-    pub(crate) const fn new(kind: KeywordKind) -> Self {
+    /// Creates an unambiguous and bounded keyword of the given kind
+    pub const fn new(kind: KeywordKind) -> Self {
         Self { kind, flags: 0 }
     }
 
-    pub(crate) const fn unbounded(kind: KeywordKind) -> Self {
+    /// Creates an unbounded keyword of the given kind
+    pub const fn unbounded(kind: KeywordKind) -> Self {
         Self {
             kind,
             flags: Self::UNBOUNDED,
         }
     }
 
-    pub(crate) const fn ambiguous(kind: KeywordKind) -> Self {
+    /// Creates an ambiguous keyword of the given kind
+    pub const fn ambiguous(kind: KeywordKind) -> Self {
         Self {
             kind,
             flags: Self::AMBIGUOUS,
         }
     }
 
-    pub(crate) const fn is_ambiguous(&self) -> bool {
+    const fn with_flag(self, flag: u8, toggle: bool) -> Self {
+        let flags = if toggle {
+            self.flags | flag
+        } else {
+            self.flags & !flag
+        };
+        Self { flags, ..self }
+    }
+
+    /// A builder method to toggle whether the keyword is ambiguous.
+    pub const fn with_ambiguous(self, toggle: bool) -> Self {
+        self.with_flag(Self::AMBIGUOUS, toggle)
+    }
+
+    /// A builder method to toggle whether the keyword is unbounded.
+    pub const fn with_unbounded(self, toggle: bool) -> Self {
+        self.with_flag(Self::UNBOUNDED, toggle)
+    }
+
+    /// Returns the category of the keyword
+    pub const fn kind(&self) -> KeywordKind {
+        self.kind
+    }
+
+    /// Returns a bool indicating whether the keyword might also be a part of the title
+    pub const fn is_ambiguous(&self) -> bool {
         (self.flags & Self::AMBIGUOUS) == Self::AMBIGUOUS
     }
 
+    /// Returns a bool indicating whether the keyword can be directly followed by other text
+    pub const fn is_unbounded(&self) -> bool {
+        !self.is_bounded()
+    }
+
     pub(crate) const fn is_bounded(&self) -> bool {
         (self.flags & Self::UNBOUNDED) != Self::UNBOUNDED
     }
@@ -187,11 +233,6 @@ pub(crate) static KEYWORDS: phf::Map<&'static UncasedStr, Keyword> = phf_map! {
     UncasedStr::new("Widescreen")   =>    Keyword::new(KeywordKind::Other),
     UncasedStr::new("WS")           =>    Keyword::new(KeywordKind::Other),
 
-    // Release group
-    UncasedStr::new("THORA")        =>    Keyword::new(KeywordKind::ReleaseGroup),  // special case because usually placed at the end
-    UncasedStr::new("UTW-THORA")    =>    Keyword::new(KeywordKind::ReleaseGroup),  // due to special case above, parser can't handle compound ones
-    UncasedStr::new("JPTVclub")     =>    Keyword::new(KeywordKind::ReleaseGroup),  // usually at the end
-
     // Release information
     UncasedStr::new("Batch")        =>    Keyword::new(KeywordKind::ReleaseInformation),
     UncasedStr::new("Complete")     =>    Keyword::new(KeywordKind::ReleaseInformation),
@@ -350,3 +391,109 @@ pub(crate) static KEYWORDS: phf::Map<&'static UncasedStr, Keyword> = phf_map! {
     UncasedStr::new("Vol")          =>    Keyword::new(KeywordKind::Volume),
     UncasedStr::new("Volume")       =>    Keyword::new(KeywordKind::Volume),
 };
+
+/// A keyword dictionary that can be extended at runtime.
+///
+/// The dictionary is layered over the built-in keywords, so entries can be added,
+/// overridden or disabled without affecting the rest of the built-in keywords.
+/// Keywords are matched case insensitively.
+///
+/// ```
+/// use anitomy::{ElementKind, Keyword, KeywordKind, KeywordTable, Parser};
+///
+/// let mut keywords = KeywordTable::new();
+/// keywords.insert("HIDIVE", Keyword::new(KeywordKind::Source));
+/// keywords.insert("CR", Keyword::ambiguous(KeywordKind::Source));
+///
+/// let parser = Parser::new().keywords(keywords);
+/// let elements = parser.parse("[Group] Title - 01 [HIDIVE 1080p].mkv");
+/// assert!(elements
+///     .iter()
+///     .any(|e| e.kind() == ElementKind::Source && e.value() == "HIDIVE"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeywordTable {
+    /// Overrides of the built-in keywords, a `None` value means the keyword is disabled
+    entries: BTreeMap<Uncased<'static>, Option<Keyword>>,
+    builtin: bool,
+}
+
+impl Default for KeywordTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeywordTable {
+    /// Creates a keyword dictionary that only contains the built-in keywords
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            builtin: true,
+        }
+    }
+
+    /// Creates a keyword dictionary without any of the built-in keywords
+    pub const fn empty() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            builtin: false,
+        }
+    }
+
+    /// Adds a keyword to the dictionary, replacing the built-in entry if there is one
+    pub fn insert(&mut self, value: &str, keyword: Keyword) {
+        self.entries
+            .insert(Uncased::new(value.to_owned()), Some(keyword));
+    }
+
+    /// Disables a keyword, including built-in ones
+    pub fn disable(&mut self, value: &str) {
+        self.entries.insert(Uncased::new(value.to_owned()), None);
+    }
+
+    /// Removes any added, overridden or disabled entry for the keyword.
+    ///
+    /// If it's a built-in keyword then the built-in entry is used again.
+    pub fn reset(&mut self, value: &str) {
+        self.entries.remove(UncasedStr::new(value));
+    }
+
+    /// Returns the keyword for the given value, if it's in the dictionary
+    pub fn get(&self, value: &str) -> Option<Keyword> {
+        let key = UncasedStr::new(value);
+        match self.entries.get(key) {
+            Some(keyword) => *keyword,
+            None if self.builtin => KEYWORDS.get(key).copied(),
+            None => None,
+        }
+    }
+
+    /// Returns an iterator over every keyword in the dictionary, in no particular order.
+    ///
+    /// Disabled keywords are not included.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Keyword)> + '_ {
+        let builtin = KEYWORDS
+            .entries()
+            .filter(|(key, _)| self.builtin && !self.entries.contains_key(**key))
+            .map(|(key, keyword)| (key.as_str(), *keyword));
+        let entries = self
+            .entries
+            .iter()
+            .filter_map(|(key, keyword)| Some((key.as_str(), (*keyword)?)));
+        builtin.chain(entries)
+    }
+
+    /// Returns a bool indicating whether any keyword starts with the given prefix.
+    ///
+    /// Disabled keywords are included, since they're only used to continue searching.
+    pub(crate) fn has_prefix(&self, prefix: &str) -> bool {
+        let prefix = UncasedStr::new(prefix);
+        let has_entry = self
+            .entries
+            .range::<UncasedStr, _>((Bound::Included(prefix), Bound::Unbounded))
+            .next()
+            .is_some_and(|(key, _)| key.starts_with(prefix.as_str()));
+        has_entry || (self.builtin && KEYWORDS.keys().any(|key| key.starts_with(prefix.as_str())))
+    }
+}
@@ -1,15 +1,95 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+pub(crate) mod context;
 pub(crate) mod element;
+pub(crate) mod episode;
+pub(crate) mod format;
+pub(crate) mod group;
 pub(crate) mod keyword;
+#[cfg(feature = "lua")]
+pub(crate) mod lua;
+#[cfg(feature = "node")]
+pub(crate) mod node;
 pub(crate) mod options;
+pub(crate) mod pack;
+#[cfg(feature = "rayon")]
+pub(crate) mod parallel;
 pub(crate) mod parser;
+#[cfg(feature = "std")]
+pub(crate) mod path;
+pub(crate) mod pattern;
+#[cfg(feature = "python")]
+pub(crate) mod python;
+pub(crate) mod release;
+pub(crate) mod title;
 pub(crate) mod tokenizer;
+pub(crate) mod trace;
 pub(crate) mod utils;
 
-pub use element::{Element, ElementKind, ElementObject, OwnedElementObject};
+pub use context::{ParseContext, Parsed};
+pub use element::{Confidence, Element, ElementKind, ElementObject, OwnedElementObject};
+pub use episode::{EpisodeNumber, EpisodeSet, EpisodeSuffix};
+pub use format::{Template, TemplateError, TemplateFields};
+pub use group::ReleaseGroupTable;
+pub use keyword::{Keyword, KeywordKind, KeywordTable};
+#[cfg(feature = "lua")]
+pub use lua::lua_module;
 pub use options::Options;
+pub use pack::{KeywordEntry, KeywordPack};
+#[cfg(feature = "rayon")]
+pub use parallel::{ParallelParse, ParseIter};
+pub use parser::Parser;
+#[cfg(feature = "std")]
+pub use path::PathElement;
+pub use release::{Date, NumberRange, ParsedRelease, Resolution};
+pub use title::TitleTable;
+pub use tokenizer::{Token, TokenKind};
+pub use trace::{Rejection, Rule, Trace, TracedElement};
+
+/// Splits a string into the tokens that the parser works with.
+///
+/// Tokens are split on brackets and delimiters, and known keywords are matched
+/// regardless of their surrounding delimiters. Numbers separated by some delimiters
+/// are combined into a single token (e.g. `01-02` or `1.11`).
+///
+/// ```
+/// use anitomy::TokenKind;
+///
+/// let input = "[Group] Title - 01 [x264]";
+/// let tokens = anitomy::tokenize(input);
+/// let kinds = tokens
+///     .iter()
+///     .filter(|t| t.kind() != TokenKind::Delimiter)
+///     .map(|t| (t.kind(), &input[t.span()]))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     kinds,
+///     [
+///         (TokenKind::OpenBracket, "["),
+///         (TokenKind::Text, "Group"),
+///         (TokenKind::CloseBracket, "]"),
+///         (TokenKind::Text, "Title"),
+///         (TokenKind::Number, "01"),
+///         (TokenKind::OpenBracket, "["),
+///         (TokenKind::Keyword, "x264"),
+///         (TokenKind::CloseBracket, "]"),
+///     ]
+/// );
+/// ```
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    tokenizer::Tokenizer::new(input).tokens()
+}
 
 /// Parses a string into its element components with the given options.
 ///
@@ -19,8 +99,7 @@ pub use options::Options;
 /// For best results, the string should be in composed form (NFC/NFKC)
 /// for the tokenizer to work properly.
 pub fn parse_with_options(input: &str, options: Options) -> Vec<Element<'_>> {
-    let tokens = tokenizer::Tokenizer::new(input).tokens();
-    parser::parse_with_options(tokens, options)
+    Parser::new().options(options).parse(input)
 }
 
 /// Parses a string into its element components with the given options
@@ -28,11 +107,165 @@ pub fn parse(input: &str) -> Vec<Element<'_>> {
     parse_with_options(input, Options::default())
 }
 
+/// Parses a path into its element components, using the parent directories as context.
+///
+/// The file name is parsed as usual with [`parse`]. If it's missing a [`ElementKind::Title`],
+/// [`ElementKind::Season`], [`ElementKind::ReleaseGroup`] or [`ElementKind::Year`] then
+/// they're filled in from the parent directories, with the closest directory taking
+/// precedence. Elements found in the file name are never overridden. Directory titles that
+/// are also keywords (e.g. `Specials`) are not used. A file name that is only a number
+/// (e.g. `05.mkv`) is read as an episode rather than a title. Nothing is parsed if the file
+/// name is not valid UTF-8, while directories that are not valid UTF-8 are ignored.
+///
+/// Each element reports the path component it was found in.
+///
+/// ```
+/// use std::path::Path;
+/// use anitomy::ElementKind;
+///
+/// let elements = anitomy::parse_path(Path::new("Show Name/Season 2/05.mkv"));
+/// let found = elements
+///     .iter()
+///     .map(|e| (e.element().kind(), e.element().value(), e.component()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     found,
+///     [
+///         (ElementKind::Title, "Show Name", 0),
+///         (ElementKind::Season, "2", 1),
+///         (ElementKind::Episode, "05", 2),
+///         (ElementKind::FileExtension, "mkv", 2),
+///     ]
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_path(path: &Path) -> Vec<PathElement<'_>> {
+    Parser::new().parse_path(path)
+}
+
+/// Parses a string into up to `count` alternative sets of elements with the given options.
+///
+/// Some names can be read in more than one way, e.g. `Tokyo ESP` where `ESP` is both a part
+/// of the title and a language keyword, or `Mob Psycho 100` where `100` could be an episode.
+/// The candidates are ranked from most to least likely, with the first one always being
+/// the same as the result of [`parse_with_options`]. Candidates are never duplicated, so
+/// fewer than `count` might be returned.
+pub fn parse_alternatives_with_options(
+    input: &str,
+    options: Options,
+    count: usize,
+) -> Vec<Vec<Element<'_>>> {
+    Parser::new()
+        .options(options)
+        .parse_alternatives(input, count)
+}
+
+/// Parses a string into up to `count` alternative sets of elements.
+///
+/// See [`parse_alternatives_with_options`] for more information.
+///
+/// ```
+/// use anitomy::ElementKind;
+///
+/// let candidates = anitomy::parse_alternatives("Tokyo ESP - 01.mkv", 3);
+/// let titles = candidates
+///     .iter()
+///     .filter_map(|elements| elements.iter().find(|e| e.kind() == ElementKind::Title))
+///     .map(|e| e.value())
+///     .collect::<Vec<_>>();
+/// assert!(titles.contains(&"Tokyo ESP"));
+/// assert!(titles.contains(&"Tokyo"));
+/// ```
+pub fn parse_alternatives(input: &str, count: usize) -> Vec<Vec<Element<'_>>> {
+    parse_alternatives_with_options(input, Options::default(), count)
+}
+
+/// Parses a string into its element components along with the rules that produced them.
+///
+/// This is meant for debugging why a name was parsed the way it was. Every element is
+/// reported along with the [`Rule`] that produced it and the tokens that the rule consumed.
+/// Some candidates that a rule considered but rejected are reported as well. The elements
+/// are the same as the ones returned by [`parse`].
+///
+/// The [`Display`](std::fmt::Display) implementation of [`Trace`] gives a report that is
+/// suitable for bug reports.
+///
+/// ```
+/// use anitomy::{ElementKind, Rule};
+///
+/// let trace = anitomy::parse_traced("[Group] Evangelion 1.11 [720p].mkv");
+/// let rules = trace
+///     .elements()
+///     .iter()
+///     .map(|e| (e.element().kind(), e.rule()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     rules,
+///     [
+///         (ElementKind::ReleaseGroup, Rule::ReleaseGroup),
+///         (ElementKind::Title, Rule::Title),
+///         (ElementKind::VideoResolution, Rule::Keyword),
+///         (ElementKind::FileExtension, Rule::FileExtension),
+///     ]
+/// );
+///
+/// // `1.11` is not read as an episode
+/// let rejected = &trace.rejected()[0];
+/// assert_eq!(rejected.rule(), Rule::FractionalEpisode);
+/// assert_eq!(trace.tokens()[rejected.token()].value(), "1.11");
+/// ```
+pub fn parse_traced(input: &str) -> Trace<'_> {
+    Parser::new().parse_traced(input)
+}
+
+/// Parses multiple strings in parallel with the given options.
+///
+/// The results are in the same order as the inputs and are the same as calling
+/// [`parse_with_options`] for each input. To parse the strings of a parallel
+/// iterator, use [`ParallelParse`] instead.
+///
+/// This requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub fn parse_many<'a>(inputs: &[&'a str], options: Options) -> Vec<Vec<Element<'a>>> {
+    Parser::new().options(options).parse_many(inputs)
+}
+
+/// Parses a batch of related names (e.g. the files of a release) consistently.
+///
+/// Each name is parsed on its own first, then the names that disagree with the rest of
+/// the batch are resolved using what the batch has in common:
+///
+/// - The title and release group that the majority of the names agree on are used
+///   for every name that contains them.
+/// - The token right after the prefix that all names share is read as the episode number
+///   if it's a distinct number in the majority of the names.
+///
+/// The results are in the same order as the inputs. A batch with a single name is the same
+/// as calling [`parse`].
+///
+/// ```
+/// use anitomy::ElementKind;
+///
+/// let names = [
+///     "[Group] Show Name 2 05.mkv",
+///     "[Group] Show Name 2 06.mkv",
+///     "[Group] Show Name 2 07.mkv",
+/// ];
+/// for elements in anitomy::parse_batch(&names) {
+///     let find = |kind| elements.iter().find(|e| e.kind() == kind).map(|e| e.value());
+///     assert_eq!(find(ElementKind::Title), Some("Show Name 2"));
+///     assert!(find(ElementKind::Episode).is_some_and(|x| x != "2"));
+/// }
+/// ```
+pub fn parse_batch<'a>(inputs: &[&'a str]) -> Vec<Vec<Element<'a>>> {
+    Parser::new().parse_batch(inputs)
+}
+
 #[cfg(feature = "wasm")]
 #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = parse))]
 pub fn parse_wasm(input: &str, options: options::JsOptions) -> Vec<element::JsElement> {
     parse_with_options(input, options.into())
         .into_iter()
-        .map(element::JsElement::from)
+        .map(|element| element::JsElement::new(input, element))
         .collect()
 }
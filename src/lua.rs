@@ -0,0 +1,107 @@
+//! The Lua bindings using `mlua`.
+//!
+//! With the `lua-module` feature these are built as a loadable module, which is opened by
+//! `require("anitomy")` and expects the library to be named `anitomy.so` somewhere in
+//! `package.cpath`. Otherwise [`lua_module`] creates the same table for an embedded Lua.
+
+use alloc::{format, string::String};
+
+use mlua::prelude::*;
+
+use crate::{
+    element::ElementObject,
+    format::{TemplateFields, FIELDS},
+    options::Options,
+    parser::Parser,
+};
+
+/// Converts a table such as `{ episode_titles = false }` into options.
+///
+/// The keys are the names of the builder methods of [`Options`].
+fn options_from_table(table: Option<LuaTable>) -> LuaResult<Options> {
+    let mut options = Options::default();
+    let Some(table) = table else {
+        return Ok(options);
+    };
+    for pair in table.pairs::<String, bool>() {
+        let (key, value) = pair?;
+        options = options
+            .toggle_by_name(&key, value)
+            .ok_or_else(|| LuaError::runtime(format!("unknown option '{key}'")))?;
+    }
+    Ok(options)
+}
+
+/// Parses a filename into a table keyed like `ElementObject`.
+///
+/// Kinds that weren't found are missing from the table. If a kind is found more than once
+/// then the last one is used.
+fn parse(lua: &Lua, (filename, options): (String, Option<LuaTable>)) -> LuaResult<LuaTable> {
+    let options = options_from_table(options)?;
+    let elements = Parser::new().options(options).parse(&filename);
+    let object = elements.iter().collect::<ElementObject<'_>>();
+    let table = lua.create_table()?;
+    for field in FIELDS {
+        if let Some(value) = object.field(field) {
+            table.set(field, &*value)?;
+        }
+    }
+    Ok(table)
+}
+
+/// Creates the table of the `anitomy` Lua module, which has a `parse(filename, options)`
+/// function.
+///
+/// This is what `require("anitomy")` returns when built as a loadable module.
+pub fn lua_module(lua: &Lua) -> LuaResult<LuaTable> {
+    let exports = lua.create_table()?;
+    exports.set("parse", lua.create_function(parse)?)?;
+    Ok(exports)
+}
+
+#[cfg(feature = "lua-module")]
+#[mlua::lua_module]
+fn anitomy(lua: &Lua) -> LuaResult<LuaTable> {
+    lua_module(lua)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_from_table() {
+        let lua = Lua::new();
+        assert_eq!(options_from_table(None).unwrap(), Options::default());
+
+        let table = lua
+            .load("{ episode_titles = false, years = true }")
+            .eval()
+            .unwrap();
+        let options = options_from_table(Some(table)).unwrap();
+        assert_eq!(options, Options::default().episode_titles(false));
+
+        let table = lua.load("{ unknown = false }").eval().unwrap();
+        assert!(options_from_table(Some(table)).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let lua = Lua::new();
+        lua.globals()
+            .set("anitomy", lua_module(&lua).unwrap())
+            .unwrap();
+        let (title, episode, episode_title): (String, String, Option<String>) = lua
+            .load(
+                r#"
+                local parsed = anitomy.parse("[Group] Title - 01 - Episode [720p].mkv", { episode_titles = false })
+                return parsed.title, parsed.episode, parsed.episode_title
+                "#,
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(title, "Title");
+        assert_eq!(episode, "01");
+        assert_eq!(episode_title, None);
+    }
+}
@@ -0,0 +1,90 @@
+//! The Node.js bindings, built as a native addon with `napi`.
+//!
+//! These mirror the WASM bindings, see `index.d.ts` for the TypeScript typings.
+
+use alloc::{string::String, vec::Vec};
+
+use napi_derive::napi;
+
+use crate::{
+    element::{Confidence, Element, ElementKind},
+    options::Options,
+    parser::Parser,
+};
+
+/// The options used when parsing, everything that is missing is enabled.
+#[napi(object, js_name = "Options")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NodeOptions {
+    pub episode: Option<bool>,
+    #[napi(js_name = "episode_title")]
+    pub episode_title: Option<bool>,
+    #[napi(js_name = "file_checksum")]
+    pub file_checksum: Option<bool>,
+    #[napi(js_name = "file_extension")]
+    pub file_extension: Option<bool>,
+    #[napi(js_name = "release_group")]
+    pub release_group: Option<bool>,
+    pub season: Option<bool>,
+    pub title: Option<bool>,
+    #[napi(js_name = "video_resolution")]
+    pub video_resolution: Option<bool>,
+    pub year: Option<bool>,
+    pub date: Option<bool>,
+}
+
+impl From<NodeOptions> for Options {
+    fn from(value: NodeOptions) -> Self {
+        Self::default()
+            .episodes(value.episode.unwrap_or(true))
+            .episode_titles(value.episode_title.unwrap_or(true))
+            .file_checksums(value.file_checksum.unwrap_or(true))
+            .file_extensions(value.file_extension.unwrap_or(true))
+            .release_groups(value.release_group.unwrap_or(true))
+            .seasons(value.season.unwrap_or(true))
+            .titles(value.title.unwrap_or(true))
+            .video_resolutions(value.video_resolution.unwrap_or(true))
+            .years(value.year.unwrap_or(true))
+            .dates(value.date.unwrap_or(true))
+    }
+}
+
+/// A parsed element.
+///
+/// The span is in UTF-16 code units, so `input.slice(start, end)` works as expected.
+#[napi(object, js_name = "Element")]
+#[derive(Debug, Clone)]
+pub struct NodeElement {
+    pub kind: ElementKind,
+    pub value: String,
+    pub start: u32,
+    pub end: u32,
+    pub confidence: Confidence,
+}
+
+impl NodeElement {
+    fn new(input: &str, element: Element<'_>) -> Self {
+        let span = element.utf16_span(input);
+        Self {
+            kind: element.kind,
+            value: element.value.into_owned(),
+            start: span.start as u32,
+            end: span.end as u32,
+            confidence: element.confidence,
+        }
+    }
+}
+
+/// Parses a string into its element components.
+///
+/// If `options` is missing then the default options are used.
+#[napi(js_name = "parse")]
+pub fn parse_node(input: String, options: Option<NodeOptions>) -> Vec<NodeElement> {
+    let options = options.map(Options::from).unwrap_or_default();
+    Parser::new()
+        .options(options)
+        .parse(&input)
+        .into_iter()
+        .map(|element| NodeElement::new(&input, element))
+        .collect()
+}
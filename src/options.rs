@@ -149,6 +149,27 @@ impl Options {
         self.toggle_flag(Self::DATE, toggle);
         self
     }
+
+    /// Toggles an option by the name of its builder method, e.g. `episode_titles`.
+    ///
+    /// Returns `None` if there is no option with that name.
+    #[cfg(any(feature = "lua", feature = "python"))]
+    pub(crate) fn toggle_by_name(self, name: &str, toggle: bool) -> Option<Self> {
+        let options = match name {
+            "episodes" => self.episodes(toggle),
+            "episode_titles" => self.episode_titles(toggle),
+            "file_checksums" => self.file_checksums(toggle),
+            "file_extensions" => self.file_extensions(toggle),
+            "release_groups" => self.release_groups(toggle),
+            "seasons" => self.seasons(toggle),
+            "titles" => self.titles(toggle),
+            "video_resolutions" => self.video_resolutions(toggle),
+            "years" => self.years(toggle),
+            "dates" => self.dates(toggle),
+            _ => return None,
+        };
+        Some(options)
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -0,0 +1,250 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::keyword::{Keyword, KeywordKind, KeywordTable};
+
+/// A collection of keywords that can be loaded into a [`KeywordTable`].
+///
+/// Packs allow keywords to be maintained outside of the code, e.g. in a TOML or JSON file.
+/// With the `serde` feature enabled a pack can be (de)serialized from any format, while the
+/// `toml` and `json` features add helpers for those formats specifically.
+///
+/// The format is a list of keyword entries and a list of disabled keywords:
+///
+/// ```toml
+/// # Keywords that are no longer recognised, including built-in ones (optional)
+/// disabled = ["BD"]
+///
+/// [[keywords]]
+/// value = "CR"
+/// category = "source"
+/// # Whether the keyword might also be a part of the title (optional)
+/// ambiguous = true
+/// # Other spellings of the same keyword (optional)
+/// aliases = ["Crunchyroll"]
+///
+/// [[keywords]]
+/// value = "1080p"
+/// category = "video_resolution"
+/// # Whether the keyword can be directly followed by other text (optional)
+/// unbounded = true
+/// ```
+///
+/// Or the equivalent in JSON:
+///
+/// ```json
+/// {
+///   "disabled": ["BD"],
+///   "keywords": [
+///     { "value": "CR", "category": "source", "ambiguous": true, "aliases": ["Crunchyroll"] },
+///     { "value": "1080p", "category": "video_resolution", "unbounded": true }
+///   ]
+/// }
+/// ```
+///
+/// The category is the snake_case name of a [`KeywordKind`] variant and values are
+/// matched case insensitively. The built-in keywords can be exported with [`KeywordPack::builtin`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeywordPack {
+    /// Keywords that are disabled when the pack is loaded
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub disabled: Vec<String>,
+    /// Keywords that are added or overridden when the pack is loaded
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub keywords: Vec<KeywordEntry>,
+}
+
+/// A single keyword entry in a [`KeywordPack`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeywordEntry {
+    /// The keyword, e.g. `1080p`
+    pub value: String,
+    /// The category of the keyword
+    pub category: KeywordKind,
+    /// Whether the keyword might also be a part of the title
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "core::ops::Not::not")
+    )]
+    pub ambiguous: bool,
+    /// Whether the keyword can be directly followed by other text
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "core::ops::Not::not")
+    )]
+    pub unbounded: bool,
+    /// Other spellings of the keyword that share the same category and flags
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub aliases: Vec<String>,
+}
+
+impl KeywordEntry {
+    /// Creates an entry without any aliases
+    pub fn new(value: impl Into<String>, keyword: Keyword) -> Self {
+        Self {
+            value: value.into(),
+            category: keyword.kind(),
+            ambiguous: keyword.is_ambiguous(),
+            unbounded: keyword.is_unbounded(),
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Returns the keyword that this entry describes
+    pub const fn keyword(&self) -> Keyword {
+        Keyword::new(self.category)
+            .with_ambiguous(self.ambiguous)
+            .with_unbounded(self.unbounded)
+    }
+
+    /// Returns an iterator over the value and all of its aliases
+    pub fn values(&self) -> impl Iterator<Item = &str> {
+        core::iter::once(self.value.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+impl KeywordPack {
+    /// Returns a pack with every built-in keyword.
+    ///
+    /// Loading this pack into [`KeywordTable::empty`] results in a dictionary
+    /// that is equivalent to [`KeywordTable::new`].
+    pub fn builtin() -> Self {
+        KeywordTable::new().to_pack()
+    }
+
+    /// Parses a pack from a TOML string
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    /// Serializes the pack into a TOML string
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Parses a pack from a JSON string
+    #[cfg(feature = "json")]
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serializes the pack into a pretty printed JSON string
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl KeywordTable {
+    /// Loads the keywords of a pack into the dictionary.
+    ///
+    /// Disabled keywords are applied first, so a pack can disable a built-in keyword
+    /// and then add it back with a different category or flags.
+    pub fn load(&mut self, pack: &KeywordPack) {
+        for value in &pack.disabled {
+            self.disable(value);
+        }
+        for entry in &pack.keywords {
+            let keyword = entry.keyword();
+            for value in entry.values() {
+                self.insert(value, keyword);
+            }
+        }
+    }
+
+    /// Exports every keyword in the dictionary into a pack.
+    ///
+    /// The entries are sorted by category and then by value.
+    pub fn to_pack(&self) -> KeywordPack {
+        let mut keywords = self
+            .iter()
+            .map(|(value, keyword)| KeywordEntry::new(value, keyword))
+            .collect::<Vec<_>>();
+        keywords.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.value.cmp(&b.value))
+        });
+        KeywordPack {
+            disabled: Vec::new(),
+            keywords,
+        }
+    }
+}
+
+impl From<&KeywordPack> for KeywordTable {
+    /// Creates a dictionary with the built-in keywords and the keywords of the pack
+    fn from(pack: &KeywordPack) -> Self {
+        let mut table = Self::new();
+        table.load(pack);
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_builtin_round_trip() {
+        let pack = KeywordPack::builtin();
+        assert!(!pack.keywords.is_empty());
+
+        let mut table = KeywordTable::empty();
+        table.load(&pack);
+        assert_eq!(table.to_pack(), pack);
+        for (value, keyword) in KeywordTable::new().iter() {
+            assert_eq!(table.get(value), Some(keyword), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_load_pack() {
+        let pack = KeywordPack {
+            disabled: vec!["BD".into(), "CR".into()],
+            keywords: vec![KeywordEntry {
+                aliases: vec!["Crunchyroll".into()],
+                ..KeywordEntry::new("CR", Keyword::ambiguous(KeywordKind::Source))
+            }],
+        };
+        let table = KeywordTable::from(&pack);
+        assert_eq!(table.get("bd"), None);
+        assert_eq!(
+            table.get("cr"),
+            Some(Keyword::ambiguous(KeywordKind::Source))
+        );
+        assert_eq!(
+            table.get("crunchyroll"),
+            Some(Keyword::ambiguous(KeywordKind::Source))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let pack = KeywordPack::builtin();
+        let json = pack.to_json().unwrap();
+        assert_eq!(KeywordPack::from_json(&json).unwrap(), pack);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_round_trip() {
+        let pack = KeywordPack::builtin();
+        let toml = pack.to_toml().unwrap();
+        assert_eq!(KeywordPack::from_toml(&toml).unwrap(), pack);
+    }
+}
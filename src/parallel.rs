@@ -0,0 +1,130 @@
+use rayon::iter::{
+    plumbing::{Consumer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+
+use crate::{element::Element, parser::Parser};
+
+impl Parser {
+    /// Parses multiple strings in parallel.
+    ///
+    /// See [`parse_many`](crate::parse_many) for more information.
+    pub fn parse_many<'a>(&self, inputs: &[&'a str]) -> Vec<Vec<Element<'a>>> {
+        inputs.par_iter().map(|input| self.parse(input)).collect()
+    }
+}
+
+/// A parallel iterator that parses every string of the underlying iterator.
+///
+/// This is created by [`ParallelParse::parse_with`].
+#[derive(Debug, Clone)]
+pub struct ParseIter<'p, I> {
+    base: I,
+    parser: &'p Parser,
+}
+
+impl<'a, I> ParallelIterator for ParseIter<'_, I>
+where
+    I: ParallelIterator<Item = &'a str>,
+{
+    type Item = Vec<Element<'a>>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let parser = self.parser;
+        self.base
+            .map(move |input| parser.parse(input))
+            .drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.base.opt_len()
+    }
+}
+
+impl<'a, I> IndexedParallelIterator for ParseIter<'_, I>
+where
+    I: IndexedParallelIterator<Item = &'a str>,
+{
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        let parser = self.parser;
+        self.base
+            .map(move |input| parser.parse(input))
+            .drive(consumer)
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        let parser = self.parser;
+        self.base
+            .map(move |input| parser.parse(input))
+            .with_producer(callback)
+    }
+}
+
+/// An extension trait to parse the strings of a parallel iterator.
+///
+/// ```
+/// use anitomy::{ElementKind, ParallelParse, Parser};
+/// use rayon::prelude::*;
+///
+/// let names = vec!["[Group] Title - 01.mkv", "[Group] Title - 02.mkv"];
+/// let parser = Parser::new();
+/// let episodes = names
+///     .par_iter()
+///     .copied()
+///     .parse_with(&parser)
+///     .map(|elements| {
+///         elements
+///             .into_iter()
+///             .find(|e| e.kind() == ElementKind::Episode)
+///             .map(|e| e.value().to_owned())
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(episodes, [Some("01".to_owned()), Some("02".to_owned())]);
+/// ```
+pub trait ParallelParse<'a>: ParallelIterator<Item = &'a str> {
+    /// Parses every string with the given parser.
+    ///
+    /// If the underlying iterator is indexed, the results are in the same order.
+    fn parse_with(self, parser: &Parser) -> ParseIter<'_, Self> {
+        ParseIter { base: self, parser }
+    }
+}
+
+impl<'a, I: ParallelIterator<Item = &'a str>> ParallelParse<'a> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    #[test]
+    fn test_parse_many() {
+        let inputs = [
+            "[TaigaSubs]_Toradora!_(2008)_-_01v2_-_Tiger_and_Dragon_[1280x720_H.264_FLAC][1234ABCD].mkv",
+            "[Group] Title S01E03 [1080p].mkv",
+            "Evangelion 2.22 Movie 3 - 05-02.mkv",
+            "",
+        ];
+        let options = Options::default().episode_titles(false);
+        let expected = inputs
+            .iter()
+            .map(|input| crate::parse_with_options(input, options))
+            .collect::<Vec<_>>();
+        assert_eq!(crate::parse_many(&inputs, options), expected);
+
+        let parser = Parser::new().options(options);
+        let results = inputs
+            .par_iter()
+            .copied()
+            .parse_with(&parser)
+            .collect::<Vec<_>>();
+        assert_eq!(results, expected);
+    }
+}
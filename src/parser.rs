@@ -1,15 +1,150 @@
-use std::{borrow::Cow, sync::OnceLock};
-
-use regex::Regex;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    collections::BTreeMap,
+    format, vec,
+    vec::Vec,
+};
+use core::ops::Range;
 
 use crate::{
-    element::{Element, ElementKind},
-    keyword::KeywordKind,
-    tokenizer::{combine_tokens, is_dash, opposite_bracket, Token},
+    element::{Confidence, Element, ElementKind},
+    episode::EpisodeNumber,
+    group::ReleaseGroupTable,
+    keyword::{KeywordKind, KeywordTable},
+    pattern::{
+        find_number_sign_episode, is_video_resolution, match_episode_prefix,
+        match_season_and_episode,
+    },
+    title::TitleTable,
+    tokenizer::{combine_tokens, is_dash, opposite_bracket, Token, Tokenizer},
+    trace::{Rule, Trace, Tracer},
     utils::*,
     Options,
 };
 
+/// A parser with its own configuration.
+///
+/// This is useful when the defaults used by [`parse`](crate::parse) are not enough,
+/// e.g. when additional keywords need to be recognised.
+///
+/// ```
+/// use anitomy::{ElementKind, Keyword, KeywordKind, Options, Parser};
+///
+/// let mut parser = Parser::new().options(Options::default().episode_titles(false));
+/// parser
+///     .keywords_mut()
+///     .insert("ADN", Keyword::new(KeywordKind::Source));
+///
+/// let elements = parser.parse("[Group] Title - 01 [ADN 1080p].mkv");
+/// assert!(elements.iter().any(|e| e.kind() == ElementKind::Source));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    options: Options,
+    keywords: KeywordTable,
+    release_groups: ReleaseGroupTable,
+    titles: TitleTable,
+}
+
+impl Parser {
+    /// Creates a parser with the default options and the built-in keywords
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A builder method to set the options used when parsing.
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// A builder method to set the keyword dictionary used when tokenizing.
+    pub fn keywords(mut self, keywords: KeywordTable) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /// Returns the keyword dictionary for modification
+    pub fn keywords_mut(&mut self) -> &mut KeywordTable {
+        &mut self.keywords
+    }
+
+    /// A builder method to set the dictionary of known release groups.
+    pub fn release_groups(mut self, release_groups: ReleaseGroupTable) -> Self {
+        self.release_groups = release_groups;
+        self
+    }
+
+    /// Returns the dictionary of known release groups for modification
+    pub fn release_groups_mut(&mut self) -> &mut ReleaseGroupTable {
+        &mut self.release_groups
+    }
+
+    /// A builder method to set the dictionary of known titles.
+    pub fn titles(mut self, titles: TitleTable) -> Self {
+        self.titles = titles;
+        self
+    }
+
+    /// Returns the dictionary of known titles for modification
+    pub fn titles_mut(&mut self) -> &mut TitleTable {
+        &mut self.titles
+    }
+
+    /// Splits a string into tokens using this parser's keywords.
+    ///
+    /// See [`tokenize`](crate::tokenize) for more information.
+    pub fn tokenize<'a>(&self, input: &'a str) -> Vec<Token<'a>> {
+        Tokenizer::with_keywords(input, &self.keywords).tokens()
+    }
+
+    /// Splits a string into tokens using this parser's keywords, reusing the given buffer.
+    pub(crate) fn tokenize_into<'a>(&self, input: &'a str, tokens: &mut Vec<Token<'a>>) {
+        Tokenizer::with_keywords(input, &self.keywords).tokens_into(tokens)
+    }
+
+    /// Parses a string into its element components.
+    ///
+    /// See [`parse_with_options`](crate::parse_with_options) for more information.
+    pub fn parse<'a>(&self, input: &'a str) -> Vec<Element<'a>> {
+        parse_tokens(self.tokenize(input), self)
+    }
+
+    /// Parses a string into up to `count` alternative sets of elements.
+    ///
+    /// See [`parse_alternatives_with_options`](crate::parse_alternatives_with_options)
+    /// for more information.
+    pub fn parse_alternatives<'a>(&self, input: &'a str, count: usize) -> Vec<Vec<Element<'a>>> {
+        parse_alternatives(self.tokenize(input), self, count)
+    }
+
+    /// Parses a string into its element components along with the rules that produced them.
+    ///
+    /// See [`parse_traced`](crate::parse_traced) for more information.
+    pub fn parse_traced<'a>(&self, input: &'a str) -> Trace<'a> {
+        let tokens = self.tokenize(input);
+        let mut tracer = Tracer::enabled();
+        let mut results = Vec::new();
+        let reading = Reading::default();
+        parse_passes(
+            &mut tokens.clone(),
+            self,
+            &reading,
+            &mut results,
+            &mut tracer,
+        );
+        tracer.finish(tokens, results)
+    }
+
+    /// Parses a batch of related names consistently.
+    ///
+    /// See [`parse_batch`](crate::parse_batch) for more information.
+    pub fn parse_batch<'a>(&self, inputs: &[&'a str]) -> Vec<Vec<Element<'a>>> {
+        let tokens = inputs.iter().map(|input| self.tokenize(input)).collect();
+        parse_batch(tokens, self)
+    }
+}
+
 fn is_token_isolated(tokens: &[Token<'_>], index: usize) -> bool {
     let Some(previous) = find_prev_token(tokens, Some(index), |t| t.is_not_delimiter()) else {
         return false;
@@ -29,19 +164,45 @@ fn is_valid_episode_number(s: &str) -> bool {
     !s.is_empty() && s.len() <= 4 && s.bytes().all(|x| x.is_ascii_digit())
 }
 
-fn is_japanese_number(ch: char) -> bool {
-    matches!(
-        ch,
-        '〇' | '一' | '二' | '三' | '四' | '五' | '六' | '七' | '八' | '九' | '十' | '百' | '千'
-    )
+/// Checks if the non-ASCII string is a short kanji or fullwidth number (e.g. `十二` or `１２`)
+fn is_japanese_number_of_length(s: &str, max_codepoints: usize) -> bool {
+    let codepoints = s.chars().count();
+    codepoints > 0 && codepoints <= max_codepoints && parse_number(s).is_some()
 }
 
 fn is_valid_japanese_episode(s: &str) -> bool {
     if s.is_ascii() {
         is_valid_episode_number(s)
     } else {
-        let codepoints = s.chars().count();
-        codepoints > 0 && codepoints <= 4 && s.chars().all(is_japanese_number)
+        is_japanese_number_of_length(s, 4)
+    }
+}
+
+fn is_valid_japanese_season(s: &str) -> bool {
+    if s.is_ascii() {
+        (1..=2).contains(&s.len()) && s.bytes().all(|x| x.is_ascii_digit())
+    } else {
+        is_japanese_number_of_length(s, 3)
+    }
+}
+
+/// Creates an element from a part of the token that might be the upper bound of a range.
+///
+/// A part is considered to end a range if it's preceded by a `-` or `~` separator and
+/// an optional prefix, e.g. `01-12` or `S01~S02`. Other separators (e.g. `01+02` or
+/// `01&02`) denote a list.
+fn element_from_range_part<'a>(
+    kind: ElementKind,
+    token: &Token<'a>,
+    value: &'a str,
+) -> Element<'a> {
+    let element = Element::from_part(kind, token, value);
+    let start = element.span.start - token.offset;
+    let before = token.value[..start].trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if before.ends_with(['-', '~']) {
+        element.ends_range()
+    } else {
+        element
     }
 }
 
@@ -86,12 +247,77 @@ fn keyword_kind_to_element_kind(keyword: KeywordKind) -> Option<ElementKind> {
     }
 }
 
-fn parse_keywords<'a>(tokens: &mut [Token<'a>], options: &Options, results: &mut Vec<Element<'a>>) {
+/// A way of reading the ambiguous parts of a name.
+///
+/// The default reading is the one used by [`parse_tokens`], the other readings
+/// are used to generate alternative parses in [`parse_alternatives`] and to resolve
+/// names consistently in [`parse_batch`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Reading {
+    /// Positions of unenclosed ambiguous keywords that are read as a part of the title
+    as_title: Vec<usize>,
+    /// Positions of unenclosed ambiguous keywords that are read as metadata
+    as_metadata: Vec<usize>,
+    /// Whether the trailing number fallback for episodes is skipped
+    skip_last_number: bool,
+    /// The range of tokens that is read as the title
+    title: Option<Range<usize>>,
+    /// The range of tokens that is read as the release group
+    release_group: Option<Range<usize>>,
+    /// The index of the token that is read as the episode number
+    episode: Option<usize>,
+}
+
+/// A single decision that differs from the default reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    KeywordAsTitle(usize),
+    KeywordAsMetadata(usize),
+    SkipLastNumber,
+}
+
+impl Reading {
+    fn from_choices(choices: &[Choice], mask: u32) -> Option<Self> {
+        let mut reading = Self::default();
+        let selected = choices
+            .iter()
+            .enumerate()
+            .filter_map(|(index, choice)| (mask & (1 << index) != 0).then_some(*choice));
+        for choice in selected {
+            match choice {
+                Choice::KeywordAsTitle(position) => reading.as_title.push(position),
+                Choice::KeywordAsMetadata(position) => reading.as_metadata.push(position),
+                Choice::SkipLastNumber => reading.skip_last_number = true,
+            }
+        }
+        // A keyword can't be read as both a title and metadata
+        if reading
+            .as_title
+            .iter()
+            .any(|x| reading.as_metadata.contains(x))
+        {
+            None
+        } else {
+            Some(reading)
+        }
+    }
+}
+
+fn parse_keywords<'a>(
+    tokens: &mut [Token<'a>],
+    options: &Options,
+    reading: &Reading,
+    results: &mut Vec<Element<'a>>,
+) {
     for token in tokens.iter_mut().filter(|t| t.is_free()) {
         let Some(keyword) = token.keyword else {
             continue;
         };
 
+        if reading.as_title.contains(&token.position) {
+            continue;
+        }
+
         if keyword.kind == KeywordKind::ReleaseGroup && !options.parse_release_group() {
             continue;
         }
@@ -103,41 +329,37 @@ fn parse_keywords<'a>(tokens: &mut [Token<'a>], options: &Options, results: &mut
             continue;
         };
 
-        if !keyword.is_ambiguous() || token.is_enclosed {
+        // Ambiguous keywords are only claimed when they're enclosed (e.g. `[ESP]`),
+        // otherwise they might be a part of the title instead (e.g. `Tokyo ESP`)
+        let confidence = if !keyword.is_ambiguous() {
+            Confidence::High
+        } else if token.is_enclosed {
+            Confidence::Medium
+        } else {
+            Confidence::Low
+        };
+        if confidence != Confidence::Low || reading.as_metadata.contains(&token.position) {
             token.mark_known();
         }
 
         let value = match keyword.kind {
-            KeywordKind::ReleaseVersion => &token.value[1..], // v2 -> 2
+            // v2 -> 2, but runtime keywords don't necessarily start with a `v`
+            KeywordKind::ReleaseVersion => {
+                token.value.strip_prefix(['v', 'V']).unwrap_or(token.value)
+            }
             _ => token.value,
         };
-        results.push(Element {
-            kind: element_kind,
-            value: Cow::Borrowed(value),
-            position: token.position,
-        });
+        results.push(Element::from_part(element_kind, token, value).with_confidence(confidence));
     }
 }
 
 fn parse_file_checksum<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
-    let (position, token) = tokens.iter_mut().enumerate().rev().find(|(_, t)| {
+    let token = tokens.iter_mut().rev().find(|t| {
         t.is_free() && t.value.len() == 8 && t.value.bytes().all(|b| b.is_ascii_hexdigit())
     })?;
 
     token.mark_known();
-    Some(Element {
-        kind: ElementKind::FileChecksum,
-        value: token.value.into(),
-        position,
-    })
-}
-
-// A video resolution can be in `1080p` or `1920x1080` format
-fn is_video_resolution(input: &str) -> bool {
-    static REGEX: OnceLock<Regex> = OnceLock::new();
-    REGEX
-        .get_or_init(|| Regex::new(r#"^\d{3,4}(?:[ip]|[xX×]\d{3,4}[ip]?)$"#).unwrap())
-        .is_match(input)
+    Some(Element::new(ElementKind::FileChecksum, token))
 }
 
 fn parse_video_resolution<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
@@ -159,7 +381,9 @@ fn parse_video_resolution<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Elemen
             .iter_mut()
             .find(|t| t.is_free() && t.is_number() && (t.value == "1080" || t.value == "720"))
         {
-            results.push(Element::new(ElementKind::VideoResolution, token));
+            results.push(
+                Element::new(ElementKind::VideoResolution, token).with_confidence(Confidence::Low),
+            );
         }
     }
 }
@@ -182,7 +406,7 @@ fn is_day(s: &str) -> bool {
     .is_some_and(|x| (1..=31).contains(&x))
 }
 
-fn parse_year<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
+fn parse_year<'a>(tokens: &mut [Token<'a>], tracer: &mut Tracer) -> Option<Element<'a>> {
     // Find a year enclosed by brackets
     if let Some(token) = tokens
         .windows(3)
@@ -210,8 +434,15 @@ fn parse_year<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
         // Check if it's isolated
         if is_token_isolated(tokens, index) {
             tokens[index].mark_known();
-            return Some(Element::new(ElementKind::Year, &tokens[index]));
+            return Some(
+                Element::new(ElementKind::Year, &tokens[index]).with_confidence(Confidence::Medium),
+            );
         }
+        tracer.reject(
+            Rule::Year,
+            &tokens[index],
+            "year is not enclosed or isolated",
+        );
     }
 
     None
@@ -240,6 +471,9 @@ fn parse_date<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
             kind: ElementKind::Date,
             value: Cow::Owned(format!("{}{}{}", year_month.value, delimiter.value, day.value)),
             position: year_month.position,
+            span: year_month.offset..day.span().end,
+            is_range_end: false,
+            confidence: Confidence::High,
         });
     }
 
@@ -247,7 +481,7 @@ fn parse_date<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
 }
 
 // A lot of this tomfoolery is because of mutation
-fn inner_parse_season<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
+fn inner_parse_season<'a>(tokens: &mut [Token<'a>], tracer: &mut Tracer) -> Option<Element<'a>> {
     let is_season_keyword =
         |token: &Token<'a>| token.keyword.is_some_and(|x| x.kind == KeywordKind::Season);
 
@@ -255,7 +489,10 @@ fn inner_parse_season<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
     while let Some([first, mid, last]) = iter.next() {
         // Check previous token for a number (e.g. 2nd Season)
         if is_season_keyword(last) && mid.is_delimiter() && first.is_free() {
-            if let Some(number) = from_ordinal_number(first.value) {
+            // Ordinals outside of the lookup table (e.g. 11th Season) keep their raw value
+            let number = from_ordinal_number(first.value)
+                .or_else(|| parse_ordinal_number(first.value).map(|_| first.value));
+            if let Some(number) = number {
                 last.mark_known();
                 mid.mark_known();
                 first.mark_known();
@@ -263,6 +500,9 @@ fn inner_parse_season<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
                     kind: ElementKind::Season,
                     value: number.into(),
                     position: first.position,
+                    span: first.offset..last.span().end,
+                    is_range_end: false,
+                    confidence: Confidence::High,
                 });
             }
         }
@@ -271,9 +511,17 @@ fn inner_parse_season<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
             let value = if last.is_number() {
                 last.value
             } else {
-                match from_roman_number(last.value) {
+                let roman = from_roman_number(last.value).or_else(|| {
+                    parse_roman_number(last.value)
+                        .filter(|x| (1..=30).contains(x))
+                        .map(|_| last.value)
+                });
+                match roman {
                     Some(value) => value,
-                    None => continue,
+                    None => {
+                        tracer.reject(Rule::Season, last, "not a season number");
+                        continue;
+                    }
                 }
             };
             last.mark_known();
@@ -283,14 +531,17 @@ fn inner_parse_season<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
                 kind: ElementKind::Season,
                 value: value.into(),
                 position: last.position,
+                span: first.offset..last.span().end,
+                is_range_end: false,
+                confidence: Confidence::High,
             });
         }
     }
     None
 }
 
-fn parse_season<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
-    if let Some(result) = inner_parse_season(tokens) {
+fn parse_season<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, tracer: &mut Tracer) {
+    if let Some(result) = inner_parse_season(tokens, tracer) {
         results.push(result);
         return;
     }
@@ -305,37 +556,26 @@ fn parse_season<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
             if let Some(suffix) = value.strip_prefix(['S', 's']) {
                 if (1..=2).contains(&suffix.len()) && suffix.bytes().all(|x| x.is_ascii_digit()) {
                     token.mark_known();
-                    results.push(Element {
-                        kind: ElementKind::Season,
-                        value: suffix.into(),
-                        position: token.position,
-                    });
+                    results.push(element_from_range_part(ElementKind::Season, token, suffix));
                 }
             }
             // 第2期 pattern
             if let Some(prefix) = value.strip_suffix('期') {
                 let prefix = prefix.strip_prefix('第').unwrap_or(prefix);
-                if (1..=2).contains(&prefix.len()) && prefix.bytes().all(|x| x.is_ascii_digit()) {
+                if is_valid_japanese_season(prefix) {
                     token.mark_known();
-                    results.push(Element {
-                        kind: ElementKind::Season,
-                        value: prefix.into(),
-                        position: token.position,
-                    });
+                    results.push(Element::from_part(ElementKind::Season, token, prefix));
                 }
             }
         }
     }
 }
 
-fn parse_volume<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
+fn parse_volume<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, tracer: &mut Tracer) {
     // Some files have multiple volume specifiers in the name
     // The index tomfoolery is again because of mutability.
     for index in 0..tokens.len() {
-        if !tokens[index]
-            .keyword
-            .is_some_and(|k| k.kind == KeywordKind::Volume)
-        {
+        if tokens[index].keyword.map(|k| k.kind) != Some(KeywordKind::Volume) {
             continue;
         }
 
@@ -346,7 +586,8 @@ fn parse_volume<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
             continue;
         }
 
-        if parse_multi_episode_range(tokens, next, results, ElementKind::Volume) {
+        let kind = ElementKind::Volume;
+        if parse_multi_episode_range(tokens, next, results, kind, Confidence::High, tracer) {
             tokens[index].mark_known();
             tokens[next].mark_known();
             continue;
@@ -355,17 +596,17 @@ fn parse_volume<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
         let Some((prefix, suffix)) = parse_single_episode(tokens[next].value) else {
             continue;
         };
-        results.push(Element {
-            kind: ElementKind::Volume,
-            value: prefix.into(),
-            position: index,
-        });
+        results.push(Element::from_part(
+            ElementKind::Volume,
+            &tokens[next],
+            prefix,
+        ));
         if !suffix.is_empty() {
-            results.push(Element {
-                kind: ElementKind::ReleaseVersion,
-                value: suffix.into(),
-                position: index,
-            })
+            results.push(Element::from_part(
+                ElementKind::ReleaseVersion,
+                &tokens[next],
+                suffix,
+            ))
         }
         tokens[index].mark_known();
         tokens[next].mark_known();
@@ -399,23 +640,6 @@ fn parse_volume<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>) {
     // }
 }
 
-fn episode_prefix_regex() -> &'static Regex {
-    static REGEX: OnceLock<Regex> = OnceLock::new();
-    REGEX.get_or_init(|| {
-        Regex::new(r#"^(?:E|[Ee][Pp]|Eps)(\d{1,4}(?:\.5)?)(?:[vV](\d))?$"#).unwrap()
-    })
-}
-
-fn season_and_episode_regex() -> &'static Regex {
-    static REGEX: OnceLock<Regex> = OnceLock::new();
-    REGEX.get_or_init(|| Regex::new(r#"^S?(\d{1,2})(?:-S?(\d{1,2}))?(?:x|[ ._-x]?EP?)(\d{1,4})(?:-(?:EP?)?(\d{1,4}))?(?:[vV](\d))?$"#).unwrap())
-}
-
-fn number_sign_episode_regex() -> &'static Regex {
-    static REGEX: OnceLock<Regex> = OnceLock::new();
-    REGEX.get_or_init(|| Regex::new(r#"[#＃](\d{1,4})(?:[-~&+](\d{1,4}))?(?:[vV](\d))?"#).unwrap())
-}
-
 fn parse_number_in_number_episode<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
     // Number comes before another number (e.g. `8 & 10`, `01 of 24`)
     // Once again, a lot of this tom foolery is because of mutability
@@ -446,7 +670,10 @@ fn parse_number_in_number_episode<'a>(tokens: &mut [Token<'a>]) -> Option<Elemen
             other_number.mark_known();
             tokens[middle].mark_known();
             tokens[index].mark_known();
-            return Some(Element::new(ElementKind::Episode, &tokens[index]));
+            return Some(
+                Element::new(ElementKind::Episode, &tokens[index])
+                    .with_confidence(Confidence::Medium),
+            );
         }
     }
     None
@@ -477,6 +704,8 @@ fn parse_multi_episode_range<'a>(
     index: usize,
     results: &mut Vec<Element<'a>>,
     kind: ElementKind,
+    confidence: Confidence,
+    tracer: &mut Tracer,
 ) -> bool {
     if let Some((first, last)) = tokens[index].value.split_once(['-', '~', '&', '+']) {
         let token = &mut tokens[index];
@@ -486,41 +715,103 @@ fn parse_multi_episode_range<'a>(
             match lower.parse::<u16>().ok().zip(upper.parse::<u16>().ok()) {
                 // Avoid matching 000-1, 5-2, etc.
                 Some((x, y)) if x < y => {
-                    results.push(Element {
-                        kind,
-                        value: lower.into(),
-                        position: token.position,
-                    });
+                    let start = results.len();
+                    results.push(Element::from_part(kind, token, lower));
                     token.mark_known();
                     if !low_version.is_empty() {
-                        results.push(Element {
-                            kind: ElementKind::ReleaseVersion,
-                            value: low_version.into(),
-                            position: token.position,
-                        });
+                        results.push(Element::from_part(
+                            ElementKind::ReleaseVersion,
+                            token,
+                            low_version,
+                        ));
                     }
-                    results.push(Element {
-                        kind,
-                        value: upper.into(),
-                        position: token.position,
-                    });
+                    results.push(element_from_range_part(kind, token, upper));
                     if !up_version.is_empty() {
-                        results.push(Element {
-                            kind: ElementKind::ReleaseVersion,
-                            value: up_version.into(),
-                            position: token.position,
-                        });
+                        results.push(Element::from_part(
+                            ElementKind::ReleaseVersion,
+                            token,
+                            up_version,
+                        ));
+                    }
+                    for element in &mut results[start..] {
+                        element.confidence = confidence;
                     }
                     return true;
                 }
-                _ => {}
+                Some(_) => tracer.reject(Rule::MultiEpisode, token, "range is not ascending"),
+                None => {}
             }
         }
     }
     false
 }
 
-fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, kind: ElementKind) {
+/// Checks whether `next` can follow `previous` in a list of episodes (e.g. `01, 03, 05`).
+///
+/// The episodes have to be increasing and close to each other, and zero padded numbers
+/// have to keep the same width, so that other numbers (e.g. the year in `01, 2011`) aren't
+/// read as a part of the list.
+fn is_next_in_episode_list(previous: &str, next: &str) -> bool {
+    const MAX_GAP: u32 = 10;
+
+    let digits = previous.bytes().take_while(u8::is_ascii_digit).count();
+    let previous = &previous[..digits];
+    let is_padded = previous.starts_with('0') || next.starts_with('0');
+    if is_year(next) || (is_padded && previous.len() != next.len()) {
+        return false;
+    }
+    match previous.parse::<u32>().ok().zip(next.parse::<u32>().ok()) {
+        Some((previous, next)) => previous < next && next - previous <= MAX_GAP,
+        None => false,
+    }
+}
+
+/// Parses a list of comma separated numbers that follow the token at `index` (e.g. `01, 03, 05`)
+///
+/// The numbers in the list share the confidence of the last element found.
+fn parse_episode_list<'a>(
+    tokens: &mut [Token<'a>],
+    index: usize,
+    results: &mut Vec<Element<'a>>,
+    kind: ElementKind,
+) {
+    let confidence = results.last().map_or(Confidence::Low, |e| e.confidence);
+    let is_enclosed = tokens[index].is_enclosed;
+    let mut current = index;
+    while let Some(comma) = find_next_token(tokens, current, true, |t| {
+        t.is_not_delimiter() || t.value == ","
+    }) {
+        if tokens[comma].value != "," {
+            break;
+        }
+        let Some(next) = find_next_token(tokens, comma, true, |t| t.is_not_delimiter()) else {
+            break;
+        };
+        let is_valid = {
+            let token = &tokens[next];
+            token.is_free()
+                && token.is_number()
+                && token.is_enclosed == is_enclosed
+                && is_valid_episode_number(token.value)
+                && is_next_in_episode_list(tokens[current].value, token.value)
+        };
+        if !is_valid {
+            break;
+        }
+        tokens[comma].mark_known();
+        tokens[next].mark_known();
+        results.push(Element::new(kind, &tokens[next]).with_confidence(confidence));
+        current = next;
+    }
+}
+
+fn parse_episode<'a>(
+    tokens: &mut [Token<'a>],
+    reading: &Reading,
+    results: &mut Vec<Element<'a>>,
+    kind: ElementKind,
+    tracer: &mut Tracer,
+) -> Option<Rule> {
     let is_regular_episode = kind == ElementKind::Episode;
     // While it's tempting to combine these for loops into one, it's imperative that
     // these are separated out since this allows the set patterns to go through
@@ -541,16 +832,24 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
         if is_keyword {
             if let Some(next) = find_next_token(tokens, index, true, |t| t.is_not_delimiter()) {
                 if tokens[next].is_free() && tokens[next].is_mostly_numbers() {
-                    if parse_multi_episode_range(tokens, next, results, kind) {
+                    if parse_multi_episode_range(
+                        tokens,
+                        next,
+                        results,
+                        kind,
+                        Confidence::High,
+                        tracer,
+                    ) {
                         tokens[index].mark_known();
-                        return;
+                        return Some(Rule::EpisodeKeyword);
                     }
 
                     if tokens[next].is_number() {
                         tokens[index].mark_known();
                         tokens[next].mark_known();
                         results.push(Element::new(kind, &tokens[next]));
-                        return;
+                        parse_episode_list(tokens, next, results, kind);
+                        return Some(Rule::EpisodeKeyword);
                     }
                 }
             }
@@ -558,27 +857,23 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
     }
 
     for token in tokens.iter_mut().filter(|t| t.is_free()) {
-        if let Some(m) = episode_prefix_regex().captures(token.value) {
-            results.push(Element {
-                kind,
-                value: m.get(1).unwrap().as_str().into(),
-                position: token.position,
-            });
+        if let Some((value, version)) = match_episode_prefix(token.value) {
+            results.push(Element::from_part(kind, token, value));
             token.mark_known();
-            if let Some(inner) = m.get(2) {
-                results.push(Element {
-                    kind: ElementKind::ReleaseVersion,
-                    value: inner.as_str().into(),
-                    position: token.position,
-                });
+            if let Some(inner) = version {
+                results.push(Element::from_part(
+                    ElementKind::ReleaseVersion,
+                    token,
+                    inner,
+                ));
             }
-            return;
+            return Some(Rule::EpisodePrefix);
         }
     }
 
     if let Some(number) = parse_number_in_number_episode(tokens) {
         results.push(number);
-        return;
+        return Some(Rule::NumberInNumber);
     }
 
     // Single episode (e.g. 01v2)
@@ -586,66 +881,49 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
         if let Some((prefix, suffix)) = parse_single_episode(token.value) {
             if !suffix.is_empty() {
                 token.mark_known();
-                results.push(Element {
-                    kind,
-                    value: prefix.into(),
-                    position: token.position,
-                });
-                results.push(Element {
-                    kind: ElementKind::ReleaseVersion,
-                    value: suffix.into(),
-                    position: token.position,
-                });
-                return;
+                results.push(Element::from_part(kind, token, prefix));
+                results.push(Element::from_part(
+                    ElementKind::ReleaseVersion,
+                    token,
+                    suffix,
+                ));
+                return Some(Rule::SingleEpisode);
             }
         }
     }
 
     // Multi-episode, (e.g. 01-02, 03-05v2)
     for index in 0..tokens.len() {
-        if tokens[index].is_free() && parse_multi_episode_range(tokens, index, results, kind) {
-            return;
+        if tokens[index].is_free()
+            && parse_multi_episode_range(tokens, index, results, kind, Confidence::Medium, tracer)
+        {
+            return Some(Rule::MultiEpisode);
         }
     }
 
     // Season and episode (e.g. `2x01`, `S01E03`, `S01-02xE001-150`)
     for token in tokens.iter_mut().filter(|t| t.is_free()) {
-        if let Some(captures) = season_and_episode_regex().captures(token.value) {
-            if captures[1].parse::<u8>().unwrap_or_default() != 0 {
-                results.push(Element {
-                    kind: ElementKind::Season,
-                    value: captures.get(1).unwrap().as_str().into(),
-                    position: token.position,
-                });
+        if let Some(m) = match_season_and_episode(token.value) {
+            if m.season.parse::<u8>().unwrap_or_default() != 0 {
+                results.push(Element::from_part(ElementKind::Season, token, m.season));
                 token.mark_known();
-                if let Some(inner) = captures.get(2) {
-                    results.push(Element {
-                        kind: ElementKind::Season,
-                        value: inner.as_str().into(),
-                        position: token.position,
-                    });
+                if let Some(inner) = m.season_end {
+                    results
+                        .push(Element::from_part(ElementKind::Season, token, inner).ends_range());
                 }
 
-                results.push(Element {
-                    kind,
-                    value: captures.get(3).unwrap().as_str().into(),
-                    position: token.position,
-                });
-                if let Some(inner) = captures.get(4) {
-                    results.push(Element {
-                        kind,
-                        value: inner.as_str().into(),
-                        position: token.position,
-                    });
+                results.push(Element::from_part(kind, token, m.episode));
+                if let Some(inner) = m.episode_end {
+                    results.push(Element::from_part(kind, token, inner).ends_range());
                 }
-                if let Some(inner) = captures.get(5) {
-                    results.push(Element {
-                        kind: ElementKind::ReleaseVersion,
-                        value: inner.as_str().into(),
-                        position: token.position,
-                    });
+                if let Some(inner) = m.version {
+                    results.push(Element::from_part(
+                        ElementKind::ReleaseVersion,
+                        token,
+                        inner,
+                    ));
                 }
-                return;
+                return Some(Rule::SeasonAndEpisode);
             }
         }
     }
@@ -662,34 +940,26 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
         if token.is_free() && token.is_number() {
             token.mark_known();
             results.push(Element::new(kind, token));
-            return;
+            return Some(Rule::TypeAndEpisode);
         }
     }
 
     // Number sign, e.g. #01 or #02-03v2
     for token in tokens.iter_mut().filter(|t| t.is_free()) {
-        if let Some(captures) = number_sign_episode_regex().captures(token.value) {
+        if let Some((value, end, version)) = find_number_sign_episode(token.value) {
             token.mark_known();
-            results.push(Element {
-                kind,
-                value: captures.get(1).unwrap().as_str().into(),
-                position: token.position,
-            });
-            if let Some(inner) = captures.get(2) {
-                results.push(Element {
-                    kind,
-                    value: inner.as_str().into(),
-                    position: token.position,
-                });
+            results.push(Element::from_part(kind, token, value));
+            if let Some(inner) = end {
+                results.push(element_from_range_part(kind, token, inner));
             }
-            if let Some(inner) = captures.get(3) {
-                results.push(Element {
-                    kind: ElementKind::ReleaseVersion,
-                    value: inner.as_str().into(),
-                    position: token.position,
-                });
+            if let Some(inner) = version {
+                results.push(Element::from_part(
+                    ElementKind::ReleaseVersion,
+                    token,
+                    inner,
+                ));
             }
-            return;
+            return Some(Rule::NumberSign);
         }
     }
     // Japanese counter (e.g. `第01話`)
@@ -698,12 +968,8 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
             let prefix = prefix.strip_prefix('第').unwrap_or(prefix);
             if is_valid_japanese_episode(prefix) {
                 token.mark_known();
-                results.push(Element {
-                    kind,
-                    value: prefix.into(),
-                    position: token.position,
-                });
-                return;
+                results.push(Element::from_part(kind, token, prefix));
+                return Some(Rule::JapaneseCounter);
             }
         }
     }
@@ -746,6 +1012,10 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
                     && is_token_isolated(tokens, next)
             };
             if !is_valid {
+                if tokens[next].is_number() {
+                    let reason = "not an isolated episode number";
+                    tracer.reject(Rule::EquivalentNumbers, &tokens[next], reason);
+                }
                 continue;
             }
 
@@ -767,9 +1037,9 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
 
             tokens[next].mark_known();
             tokens[index].mark_known();
-            results.push(Element::new(b, &tokens[next]));
-            results.push(Element::new(a, &tokens[index]));
-            return;
+            results.push(Element::new(b, &tokens[next]).with_confidence(Confidence::Medium));
+            results.push(Element::new(a, &tokens[index]).with_confidence(Confidence::Medium));
+            return Some(Rule::EquivalentNumbers);
         }
     }
 
@@ -784,12 +1054,14 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
             continue;
         }
 
-        if let Some(token) = tokens.iter_mut().skip(index).find(|x| x.is_not_delimiter()) {
+        if let Some(next) = find_next_token(tokens, index, false, |t| t.is_not_delimiter()) {
+            let token = &mut tokens[next];
             if token.is_number() && token.is_free() {
                 token.mark_known();
                 results.push(Element::new(kind, token));
                 tokens[index].mark_known();
-                return;
+                parse_episode_list(tokens, next, results, kind);
+                return Some(Rule::SeparatedNumber);
             }
         }
     }
@@ -802,8 +1074,11 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
             // `Tokyo Magnitude 8.0`) or a keyword (e.g. `5.1`).
             if second == "5" && is_valid_episode_number(first) {
                 token.mark_known();
-                results.push(Element::new(kind, token));
-                return;
+                results.push(Element::new(kind, token).with_confidence(Confidence::Medium));
+                return Some(Rule::FractionalEpisode);
+            }
+            if is_valid_episode_number(first) && is_valid_episode_number(second) {
+                tracer.reject(Rule::FractionalEpisode, token, "fraction is not `.5`");
             }
         }
     }
@@ -817,9 +1092,9 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
                 && middle.is_free()
                 && middle.is_number()
             {
-                results.push(Element::new(kind, middle));
+                results.push(Element::new(kind, middle).with_confidence(Confidence::Medium));
                 middle.mark_known();
-                return;
+                return Some(Rule::IsolatedNumber);
             }
         }
     }
@@ -829,12 +1104,16 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
         if let Some(prefix) = token.value.strip_suffix(['A', 'B', 'C', 'a', 'b', 'c']) {
             if is_valid_episode_number(prefix) {
                 token.mark_known();
-                results.push(Element::new(kind, token));
-                return;
+                results.push(Element::new(kind, token).with_confidence(Confidence::Medium));
+                return Some(Rule::PartialEpisode);
             }
         }
     }
 
+    if reading.skip_last_number {
+        return None;
+    }
+
     // Last number
     // Get all the free number tokens available:
     // is_enclosed: At this point an enclosed number is not the episode number
@@ -847,6 +1126,7 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
             .iter()
             .all(|t| t.is_enclosed || t.is_delimiter())
         {
+            tracer.reject(Rule::LastNumber, &tokens[index], "number starts the name");
             continue;
         }
 
@@ -858,6 +1138,11 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
                 && (prev.value.eq_ignore_ascii_case("movie")
                     || prev.value.eq_ignore_ascii_case("part"))
             {
+                tracer.reject(
+                    Rule::LastNumber,
+                    &tokens[index],
+                    "number follows movie or part",
+                );
                 continue;
             }
         }
@@ -865,9 +1150,11 @@ fn parse_episode<'a>(tokens: &mut [Token<'a>], results: &mut Vec<Element<'a>>, k
         // At this point this is probably the valid number
         let token = &mut tokens[index];
         token.mark_known();
-        results.push(Element::new(kind, token));
-        break;
+        results.push(Element::new(kind, token).with_confidence(Confidence::Low));
+        parse_episode_list(tokens, index, results, kind);
+        return Some(Rule::LastNumber);
     }
+    None
 }
 
 fn find_prev_token<F>(
@@ -970,21 +1257,34 @@ fn find_title<'a, 'b>(tokens: &'b mut [Token<'a>]) -> Option<&'b mut [Token<'a>]
     }
 }
 
+/// Parses the tokens of a known title, the value is `title` as written in the dictionary
+/// if there is one.
+fn parse_known_title<'a>(
+    tokens: &mut [Token<'a>],
+    range: Range<usize>,
+    title: Option<&str>,
+) -> Element<'a> {
+    let range = &mut tokens[range];
+    let value = match title {
+        Some(title) => title.to_owned(),
+        None => combine_tokens(range, crate::tokenizer::KeepDelimiters::No),
+    };
+    for token in range.iter_mut() {
+        token.mark_known();
+    }
+    Element::from_tokens(ElementKind::Title, value, range)
+}
+
 fn parse_title<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
     let range = find_title(tokens)?;
     let value = combine_tokens(range, crate::tokenizer::KeepDelimiters::No);
     if value.is_empty() {
         None
     } else {
-        let position = range.first()?.position;
-        for token in range {
+        for token in range.iter_mut() {
             token.mark_known();
         }
-        Some(Element {
-            kind: ElementKind::Title,
-            value: value.into(),
-            position,
-        })
+        Some(Element::from_tokens(ElementKind::Title, value, range))
     }
 }
 
@@ -1051,21 +1351,34 @@ fn find_release_group<'a, 'b>(tokens: &'b mut [Token<'a>]) -> Option<&'b mut [To
     }
 }
 
+fn parse_known_release_group<'a>(tokens: &mut [Token<'a>], range: Range<usize>) -> Element<'a> {
+    let range = &mut tokens[range];
+    let value = combine_tokens(range, crate::tokenizer::KeepDelimiters::Yes);
+    for token in range.iter_mut() {
+        token.mark_known();
+    }
+    Element::from_tokens(ElementKind::ReleaseGroup, value, range)
+}
+
 fn parse_release_group<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
     let range = find_release_group(tokens)?;
     let value = combine_tokens(range, crate::tokenizer::KeepDelimiters::Yes);
     if value.is_empty() {
         None
     } else {
-        let position = range.first()?.position;
-        for token in range {
+        for token in range.iter_mut() {
             token.mark_known();
         }
-        Some(Element {
-            kind: ElementKind::ReleaseGroup,
-            value: value.into(),
-            position,
-        })
+        // Groups that aren't enclosed come from the `Title-Group.mkv` fallback
+        let confidence = if range.iter().all(|t| t.is_enclosed) {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        };
+        Some(
+            Element::from_tokens(ElementKind::ReleaseGroup, value, range)
+                .with_confidence(confidence),
+        )
     }
 }
 
@@ -1119,86 +1432,396 @@ fn parse_episode_title<'a>(tokens: &mut [Token<'a>]) -> Option<Element<'a>> {
     if value.is_empty() {
         None
     } else {
-        let position = range.first()?.position;
-        for token in range {
+        for token in range.iter_mut() {
             token.mark_known();
         }
-        Some(Element {
-            kind: ElementKind::EpisodeTitle,
-            value: value.into(),
-            position,
+        Some(
+            Element::from_tokens(ElementKind::EpisodeTitle, value, range)
+                .with_confidence(Confidence::Medium),
+        )
+    }
+}
+
+fn parse_tokens<'a>(tokens: Vec<Token<'a>>, parser: &Parser) -> Vec<Element<'a>> {
+    parse_with_reading(tokens, parser, &Reading::default())
+}
+
+/// Returns up to `count` distinct parses, starting with the default one.
+///
+/// The alternatives are made by changing how the ambiguous parts of the name are read,
+/// i.e. whether an unenclosed ambiguous keyword is a part of the title or metadata and whether
+/// a trailing number is an episode or a part of the title. Alternatives with fewer changes
+/// from the default reading are ranked first.
+fn parse_alternatives<'a>(
+    tokens: Vec<Token<'a>>,
+    parser: &Parser,
+    count: usize,
+) -> Vec<Vec<Element<'a>>> {
+    // The number of choices and readings are bounded so the combinations stay reasonable
+    const MAX_CHOICES: usize = 12;
+    const MAX_READINGS: usize = 64;
+
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let default = parse_with_reading(tokens.clone(), parser, &Reading::default());
+    let mut choices = Vec::new();
+    // This goes first so it isn't truncated when there are a lot of keywords
+    if default
+        .iter()
+        .any(|e| e.kind == ElementKind::Episode && e.confidence == Confidence::Low)
+    {
+        choices.push(Choice::SkipLastNumber);
+    }
+    choices.extend(
+        tokens
+            .iter()
+            .filter(|t| {
+                !t.is_enclosed
+                    && t.keyword.is_some_and(|k| {
+                        k.is_ambiguous() && keyword_kind_to_element_kind(k.kind).is_some()
+                    })
+            })
+            .flat_map(|t| {
+                [
+                    Choice::KeywordAsTitle(t.position),
+                    Choice::KeywordAsMetadata(t.position),
+                ]
+            }),
+    );
+    choices.truncate(MAX_CHOICES);
+
+    let mut results = vec![default];
+    let readings = masks_by_popcount(choices.len() as u32)
+        .filter_map(|mask| Reading::from_choices(&choices, mask))
+        .take(MAX_READINGS);
+    for reading in readings {
+        if results.len() >= count {
+            break;
+        }
+        let elements = parse_with_reading(tokens.clone(), parser, &reading);
+        if !results.contains(&elements) {
+            results.push(elements);
+        }
+    }
+    results
+}
+
+/// Returns every non-zero mask of `len` bits, ordered by the number of bits that are set
+fn masks_by_popcount(len: u32) -> impl Iterator<Item = u32> {
+    (1..=len).flat_map(move |ones| {
+        // Gosper's hack, which goes through the masks with the same number of bits in order
+        let mut next = Some((1u32 << ones) - 1);
+        core::iter::from_fn(move || {
+            let mask = next?;
+            let lowest = mask & mask.wrapping_neg();
+            let ripple = mask + lowest;
+            let candidate = (((ripple ^ mask) >> 2) / lowest) | ripple;
+            next = (candidate < 1 << len).then_some(candidate);
+            Some(mask)
         })
+    })
+}
+
+/// Returns the value of the given kind that the majority of the parses agree on
+fn find_consensus<'b>(parses: &'b [Vec<Element<'_>>], kind: ElementKind) -> Option<&'b str> {
+    let mut counts = BTreeMap::new();
+    for elements in parses {
+        if let Some(element) = elements.iter().find(|e| e.kind == kind) {
+            *counts.entry(element.value()).or_insert(0usize) += 1;
+        }
     }
+    counts
+        .into_iter()
+        .find(|&(_, count)| count * 2 > parses.len())
+        .map(|(value, _)| value)
 }
 
-pub(crate) fn parse_with_options(mut tokens: Vec<Token<'_>>, options: Options) -> Vec<Element<'_>> {
+/// Finds the token that varies between the names and is read as their episode number.
+///
+/// Related names (e.g. the files of a release) usually share the same prefix up until
+/// the episode number, e.g. `[Group] Title - 01 [720p]` and `[Group] Title - 02 [1080p]`.
+/// The first token after the shared prefix is the episode number if it's a distinct
+/// number in the majority of the names. Roman numerals are not numbers here, since
+/// they're usually a part of the title.
+fn find_varying_episodes(tokens: &[Vec<Token<'_>>]) -> Vec<Option<usize>> {
+    let words = tokens
+        .iter()
+        .map(|tokens| {
+            (0..tokens.len())
+                .filter(|&index| tokens[index].is_not_delimiter())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let value_at = |name: usize, word: usize| words[name].get(word).map(|&i| tokens[name][i].value);
+    let prefix = (0..)
+        .take_while(|&word| {
+            let first = value_at(0, word);
+            first.is_some() && (1..words.len()).all(|name| value_at(name, word) == first)
+        })
+        .count();
+
+    let candidates = (0..words.len())
+        .map(|name| {
+            let index = *words[name].get(prefix)?;
+            let token = &tokens[name][index];
+            // Only actual numbers, e.g. not the roman numerals in `Rocky II`
+            if !token.is_free() || !token.is_mostly_numbers() {
+                return None;
+            }
+            EpisodeNumber::parse(token.value).map(|number| (index, number))
+        })
+        .collect::<Vec<_>>();
+
+    let numbers = candidates
+        .iter()
+        .flatten()
+        .map(|(_, number)| number)
+        .collect::<Vec<_>>();
+    let distinct = numbers
+        .iter()
+        .enumerate()
+        .all(|(index, number)| !numbers[..index].contains(number));
+    if numbers.len() < 2 || numbers.len() * 2 <= tokens.len() || !distinct {
+        return vec![None; tokens.len()];
+    }
+
+    candidates
+        .into_iter()
+        .map(|candidate| candidate.map(|(index, _)| index))
+        .collect()
+}
+
+/// Parses the names and then resolves the ones that disagree with the rest of the batch.
+///
+/// The title and release group that the majority of the names agree on are read as such
+/// in every name that contains them, and the number that varies between the names is read
+/// as their episode number.
+fn parse_batch<'a>(tokens: Vec<Vec<Token<'a>>>, parser: &Parser) -> Vec<Vec<Element<'a>>> {
+    let mut results = tokens
+        .iter()
+        .map(|tokens| parse_tokens(tokens.clone(), parser))
+        .collect::<Vec<_>>();
+    if tokens.len() < 2 {
+        return results;
+    }
+
+    let title = find_consensus(&results, ElementKind::Title).map(str::to_owned);
+    let mut titles = TitleTable::new();
+    if let Some(title) = &title {
+        titles.insert(title);
+    }
+    let release_group = find_consensus(&results, ElementKind::ReleaseGroup).map(str::to_owned);
+    let mut release_groups = ReleaseGroupTable::empty();
+    if let Some(group) = &release_group {
+        release_groups.insert(group);
+    }
+    let episodes = find_varying_episodes(&tokens);
+
+    for ((tokens, elements), episode) in tokens.into_iter().zip(results.iter_mut()).zip(episodes) {
+        let value_of = |kind| elements.iter().find(|e| e.kind == kind).map(|e| e.value());
+        let mut reading = Reading::default();
+        if title.is_some() && value_of(ElementKind::Title) != title.as_deref() {
+            reading.title = titles.find_anywhere(&tokens);
+        }
+        if release_group.is_some()
+            && value_of(ElementKind::ReleaseGroup) != release_group.as_deref()
+        {
+            reading.release_group = release_groups.find(&tokens);
+        }
+        if let Some(index) = episode {
+            // A token that is already read as something else (e.g. a volume) is left alone,
+            // unless it's only part of the free text that the batch is meant to correct
+            let span = tokens[index].span();
+            if !elements.iter().any(|e| {
+                !matches!(e.kind, ElementKind::Title | ElementKind::EpisodeTitle)
+                    && e.span.start <= span.start
+                    && span.end <= e.span.end
+            }) {
+                reading.episode = Some(index);
+            }
+        }
+        if reading != Reading::default() {
+            *elements = parse_with_reading(tokens, parser, &reading);
+        }
+    }
+    results
+}
+
+fn parse_with_reading<'a>(
+    mut tokens: Vec<Token<'a>>,
+    parser: &Parser,
+    reading: &Reading,
+) -> Vec<Element<'a>> {
     let mut results = Vec::new();
+    parse_passes(
+        &mut tokens,
+        parser,
+        reading,
+        &mut results,
+        &mut Tracer::default(),
+    );
+    results.sort_by_key(|e| e.position);
+    results
+}
+
+/// Parses the tokens with the default reading, replacing the contents of the given buffer
+pub(crate) fn parse_into<'a>(
+    tokens: &mut [Token<'a>],
+    parser: &Parser,
+    results: &mut Vec<Element<'a>>,
+) {
+    results.clear();
+    let reading = Reading::default();
+    parse_passes(tokens, parser, &reading, results, &mut Tracer::default());
+    results.sort_by_key(|e| e.position);
+}
+
+/// Runs every pass over the tokens, pushing the elements in the order they were found
+fn parse_passes<'a>(
+    tokens: &mut [Token<'a>],
+    parser: &Parser,
+    reading: &Reading,
+    results: &mut Vec<Element<'a>>,
+    tracer: &mut Tracer,
+) {
+    let options = parser.options;
     if options.parse_file_extension() {
-        if let Some(el) = parse_file_extension(&mut tokens) {
+        tracer.begin(tokens, results);
+        if let Some(el) = parse_file_extension(tokens) {
             results.push(el);
         }
+        tracer.end(Rule::FileExtension, tokens, results);
     }
 
-    parse_keywords(&mut tokens, &options, &mut results);
+    if options.parse_title() {
+        let known = match reading.title.clone() {
+            Some(range) => Some((range, None)),
+            None => parser
+                .titles
+                .find(tokens)
+                .map(|(range, title)| (range, Some(title))),
+        };
+        if let Some((range, title)) = known {
+            tracer.begin(tokens, results);
+            results.push(parse_known_title(tokens, range, title));
+            tracer.end(Rule::KnownTitle, tokens, results);
+        }
+    }
+
+    if options.parse_release_group() {
+        let range = reading
+            .release_group
+            .clone()
+            .or_else(|| parser.release_groups.find(tokens));
+        if let Some(range) = range {
+            tracer.begin(tokens, results);
+            results.push(parse_known_release_group(tokens, range));
+            tracer.end(Rule::KnownReleaseGroup, tokens, results);
+        }
+    }
+
+    if options.parse_episode() {
+        if let Some(index) = reading.episode {
+            tracer.begin(tokens, results);
+            tokens[index].mark_known();
+            results.push(
+                Element::new(ElementKind::Episode, &tokens[index])
+                    .with_confidence(Confidence::Medium),
+            );
+            tracer.end(Rule::BatchEpisode, tokens, results);
+        }
+    }
+
+    tracer.begin(tokens, results);
+    parse_keywords(tokens, &options, reading, results);
+    tracer.end(Rule::Keyword, tokens, results);
 
     if options.parse_file_checksum() {
-        if let Some(el) = parse_file_checksum(&mut tokens) {
+        tracer.begin(tokens, results);
+        if let Some(el) = parse_file_checksum(tokens) {
             results.push(el);
         }
+        tracer.end(Rule::FileChecksum, tokens, results);
     }
 
     if options.parse_video_resolution() {
-        parse_video_resolution(&mut tokens, &mut results);
+        tracer.begin(tokens, results);
+        parse_video_resolution(tokens, results);
+        tracer.end(Rule::VideoResolution, tokens, results);
     }
 
     if options.parse_date() {
-        if let Some(el) = parse_date(&mut tokens) {
+        tracer.begin(tokens, results);
+        if let Some(el) = parse_date(tokens) {
             results.push(el);
         }
+        tracer.end(Rule::Date, tokens, results);
     }
 
     if options.parse_year() {
-        if let Some(el) = parse_year(&mut tokens) {
+        tracer.begin(tokens, results);
+        if let Some(el) = parse_year(tokens, tracer) {
             results.push(el);
         }
+        tracer.end(Rule::Year, tokens, results);
     }
 
     if options.parse_season() {
-        parse_season(&mut tokens, &mut results);
+        tracer.begin(tokens, results);
+        parse_season(tokens, results, tracer);
+        tracer.end(Rule::Season, tokens, results);
     }
 
     if options.parse_episode() {
-        parse_volume(&mut tokens, &mut results);
-        parse_episode(&mut tokens, &mut results, ElementKind::Episode);
+        tracer.begin(tokens, results);
+        parse_volume(tokens, results, tracer);
+        tracer.end(Rule::Volume, tokens, results);
+        if reading.episode.is_none() {
+            tracer.begin(tokens, results);
+            let kind = ElementKind::Episode;
+            if let Some(rule) = parse_episode(tokens, reading, results, kind, tracer) {
+                tracer.end(rule, tokens, results);
+            }
+        }
     }
 
-    if options.parse_title() {
-        if let Some(title) = parse_title(&mut tokens) {
+    if options.parse_title() && !results.iter().any(|e| e.kind == ElementKind::Title) {
+        tracer.begin(tokens, results);
+        if let Some(title) = parse_title(tokens) {
             results.push(title);
         }
+        tracer.end(Rule::Title, tokens, results);
     }
 
     if options.parse_release_group() && !results.iter().any(|e| e.kind == ElementKind::ReleaseGroup)
     {
-        if let Some(group) = parse_release_group(&mut tokens) {
+        tracer.begin(tokens, results);
+        if let Some(group) = parse_release_group(tokens) {
             results.push(group);
         }
+        tracer.end(Rule::ReleaseGroup, tokens, results);
     }
 
     let has_episode = results.iter().any(|e| e.kind == ElementKind::Episode);
 
     if has_episode {
         if options.parse_episode_title() {
-            if let Some(title) = parse_episode_title(&mut tokens) {
+            tracer.begin(tokens, results);
+            if let Some(title) = parse_episode_title(tokens) {
                 results.push(title);
             }
+            tracer.end(Rule::EpisodeTitle, tokens, results);
         }
 
         if options.parse_episode() {
-            parse_episode(&mut tokens, &mut results, ElementKind::EpisodeAlt)
+            tracer.begin(tokens, results);
+            let kind = ElementKind::EpisodeAlt;
+            if let Some(rule) = parse_episode(tokens, reading, results, kind, tracer) {
+                tracer.end(rule, tokens, results);
+            }
         }
     }
-
-    results.sort_by_key(|e| e.position);
-    results
 }
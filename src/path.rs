@@ -0,0 +1,199 @@
+use std::path::{Component, Path};
+
+use crate::{
+    element::{Confidence, Element, ElementKind},
+    parser::Parser,
+};
+
+/// The kinds of elements that can be inherited from a parent directory, in the order they're looked up.
+const INHERITED_KINDS: [ElementKind; 4] = [
+    ElementKind::Title,
+    ElementKind::Season,
+    ElementKind::ReleaseGroup,
+    ElementKind::Year,
+];
+
+/// An element that was parsed from a component of a path.
+///
+/// See [`parse_path`](crate::parse_path) for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathElement<'a> {
+    element: Element<'a>,
+    component: usize,
+}
+
+impl<'a> PathElement<'a> {
+    /// Returns the parsed element.
+    ///
+    /// Note that the span of the element is relative to the path component it was found in.
+    pub fn element(&self) -> &Element<'a> {
+        &self.element
+    }
+
+    /// Returns the index of the path component this element was found in.
+    ///
+    /// This is an index into the iterator returned by [`Path::components`].
+    pub fn component(&self) -> usize {
+        self.component
+    }
+
+    /// Consumes this and returns the parsed element
+    pub fn into_element(self) -> Element<'a> {
+        self.element
+    }
+}
+
+impl Parser {
+    /// Parses a path into its element components, using the parent directories as context.
+    ///
+    /// See [`parse_path`](crate::parse_path) for more information.
+    pub fn parse_path<'a>(&self, path: &'a Path) -> Vec<PathElement<'a>> {
+        let mut components = path
+            .components()
+            .enumerate()
+            .filter_map(|(index, component)| match component {
+                Component::Normal(name) => Some((index, name)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Skipping a file name that is not valid UTF-8 would parse a directory in its place
+        let Some((index, Some(file_name))) = components.pop().map(|(i, name)| (i, name.to_str()))
+        else {
+            return Vec::new();
+        };
+
+        let mut elements = self.parse(file_name);
+
+        // A file name that's only a number (e.g. `05.mkv`) is an episode of the series
+        // named by its directories rather than a title
+        if !elements.iter().any(|e| e.kind == ElementKind::Episode) {
+            if let Some(element) = elements.iter_mut().find(|e| {
+                e.kind == ElementKind::Title && e.value.bytes().all(|b| b.is_ascii_digit())
+            }) {
+                element.kind = ElementKind::Episode;
+                element.confidence = Confidence::Medium;
+            }
+        }
+
+        let mut results = elements
+            .into_iter()
+            .map(|element| PathElement {
+                element,
+                component: index,
+            })
+            .collect::<Vec<_>>();
+
+        let mut missing = INHERITED_KINDS
+            .into_iter()
+            .filter(|&kind| !results.iter().any(|e| e.element.kind == kind))
+            .collect::<Vec<_>>();
+
+        // The closest directory takes precedence over the ones further up
+        for &(index, name) in components.iter().rev() {
+            if missing.is_empty() {
+                break;
+            }
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+
+            let elements = self.parse(name);
+            missing.retain(|&kind| {
+                let Some(element) = elements
+                    .iter()
+                    .find(|e| e.kind == kind && is_inheritable(e, &elements))
+                else {
+                    return true;
+                };
+                results.push(PathElement {
+                    element: element.clone(),
+                    component: index,
+                });
+                false
+            });
+        }
+
+        results.sort_by_key(|e| (e.component, e.element.position));
+        results
+    }
+}
+
+/// Checks whether an element of a directory can be inherited by the file name.
+///
+/// Directory titles that are also keywords (e.g. `Specials`) are not inherited, since
+/// these directories are usually used to group files rather than name a series.
+fn is_inheritable(element: &Element<'_>, elements: &[Element<'_>]) -> bool {
+    element.kind != ElementKind::Title
+        || !elements
+            .iter()
+            .any(|e| e.kind != ElementKind::Title && e.span == element.span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    fn find<'a>(elements: &'a [PathElement<'_>], kind: ElementKind) -> Option<(&'a str, usize)> {
+        elements
+            .iter()
+            .find(|e| e.element().kind() == kind)
+            .map(|e| (e.element().value(), e.component()))
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let parser = Parser::new();
+
+        let path = Path::new("Show Name (2019)/Season 2/05.mkv");
+        let elements = parser.parse_path(path);
+        assert_eq!(find(&elements, ElementKind::Title), Some(("Show Name", 0)));
+        assert_eq!(find(&elements, ElementKind::Year), Some(("2019", 0)));
+        assert_eq!(find(&elements, ElementKind::Season), Some(("2", 1)));
+        assert_eq!(find(&elements, ElementKind::Episode), Some(("05", 2)));
+        assert_eq!(
+            find(&elements, ElementKind::FileExtension),
+            Some(("mkv", 2))
+        );
+
+        // Elements in the file name take precedence over the directories
+        let path = Path::new("/anime/[Group] Show Name/Other Name - 03.mkv");
+        let elements = parser.parse_path(path);
+        assert_eq!(find(&elements, ElementKind::Title), Some(("Other Name", 3)));
+        assert_eq!(
+            find(&elements, ElementKind::ReleaseGroup),
+            Some(("Group", 2))
+        );
+        assert_eq!(elements.iter().filter(|e| e.component() == 1).count(), 0);
+
+        // Grouping directories are not used as titles
+        let path = Path::new("Show Name/Specials/01.mkv");
+        let elements = parser.parse_path(path);
+        assert_eq!(find(&elements, ElementKind::Title), Some(("Show Name", 0)));
+
+        // Titles that only look like a roman numeral are still titles
+        let path = Path::new("Movies/MIX.mkv");
+        let elements = parser.parse_path(path);
+        assert_eq!(find(&elements, ElementKind::Title), Some(("MIX", 1)));
+        assert_eq!(find(&elements, ElementKind::Episode), None);
+
+        assert!(parser.parse_path(Path::new("/")).is_empty());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_path_invalid_utf8() {
+        let parser = Parser::new();
+
+        let path = Path::new("Show Name/Season 2").join(OsStr::from_bytes(b"\xff05.mkv"));
+        assert!(parser.parse_path(&path).is_empty());
+
+        let path = Path::new(OsStr::from_bytes(b"\xffShow Name")).join("Season 2/05.mkv");
+        let elements = parser.parse_path(&path);
+        assert_eq!(find(&elements, ElementKind::Title), None);
+        assert_eq!(find(&elements, ElementKind::Season), Some(("2", 1)));
+        assert_eq!(find(&elements, ElementKind::Episode), Some(("05", 2)));
+    }
+}
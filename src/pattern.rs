@@ -0,0 +1,300 @@
+//! Matchers for the fixed patterns used by the parser.
+//!
+//! Each matcher is documented with the regular expression it implements. As in those
+//! expressions, `\d` matches a decimal digit of any script rather than only ASCII digits.
+
+/// Checks if the character is a decimal digit (`\p{Nd}` as of Unicode 16.0)
+fn is_decimal_digit(ch: char) -> bool {
+    // The digits of every script are a contiguous run from zero to nine,
+    // so only the code points of the zeros are needed.
+    const ZEROS: [u32; 76] = [
+        0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66,
+        0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946,
+        0x19D0, 0x1A80, 0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0,
+        0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136,
+        0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0,
+        0x11950, 0x11BF0, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50,
+        0x16D70, 0x1CCF0, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+        0x1E5F1, 0x1E950, 0x1FBF0,
+    ];
+
+    let ch = ch as u32;
+    match ZEROS.binary_search(&ch) {
+        Ok(_) => true,
+        Err(0) => false,
+        Err(index) => ch - ZEROS[index - 1] < 10,
+    }
+}
+
+/// Splits off up to `max` leading digits, returning `None` if there are fewer than `min`
+fn take_digits(s: &str, min: usize, max: usize) -> Option<(&str, &str)> {
+    let (count, end) = s
+        .char_indices()
+        .take(max)
+        .take_while(|&(_, ch)| is_decimal_digit(ch))
+        .fold((0, 0), |(count, _), (index, ch)| {
+            (count + 1, index + ch.len_utf8())
+        });
+    (count >= min).then(|| s.split_at(end))
+}
+
+/// Matches an optional trailing release version, i.e. `(?:[vV](\d))?$`
+fn match_version(s: &str) -> Option<Option<&str>> {
+    if s.is_empty() {
+        return Some(None);
+    }
+    let rest = s.strip_prefix(['v', 'V'])?;
+    let mut chars = rest.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if is_decimal_digit(ch) => Some(Some(rest)),
+        _ => None,
+    }
+}
+
+/// Checks if the string is a video resolution in `1080p` or `1920x1080` format.
+///
+/// `^\d{3,4}(?:[ip]|[xX×]\d{3,4}[ip]?)$`
+pub(crate) fn is_video_resolution(s: &str) -> bool {
+    let Some((_, rest)) = take_digits(s, 3, 4) else {
+        return false;
+    };
+    if rest == "i" || rest == "p" {
+        return true;
+    }
+    let Some(rest) = rest.strip_prefix(['x', 'X', '×']) else {
+        return false;
+    };
+    match take_digits(rest, 3, 4) {
+        Some((_, rest)) => matches!(rest, "" | "i" | "p"),
+        None => false,
+    }
+}
+
+/// Matches an episode with a prefix, e.g. `E01`, `EP07.5` or `Eps01v2`.
+///
+/// Returns the episode and the release version, if any.
+///
+/// `^(?:E|[Ee][Pp]|Eps)(\d{1,4}(?:\.5)?)(?:[vV](\d))?$`
+pub(crate) fn match_episode_prefix(s: &str) -> Option<(&str, Option<&str>)> {
+    let mut prefixes = [
+        s.strip_prefix('E'),
+        s.strip_prefix(['E', 'e'])
+            .and_then(|x| x.strip_prefix(['P', 'p'])),
+        s.strip_prefix("Eps"),
+    ]
+    .into_iter()
+    .flatten();
+
+    prefixes.find_map(|rest| {
+        let (_, after) = take_digits(rest, 1, 4)?;
+        let after = after.strip_prefix(".5").unwrap_or(after);
+        let episode = &rest[..(rest.len() - after.len())];
+        Some((episode, match_version(after)?))
+    })
+}
+
+/// The parts of a season and episode, e.g. `S01-02xE001-150v2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SeasonAndEpisode<'a> {
+    pub(crate) season: &'a str,
+    pub(crate) season_end: Option<&'a str>,
+    pub(crate) episode: &'a str,
+    pub(crate) episode_end: Option<&'a str>,
+    pub(crate) version: Option<&'a str>,
+}
+
+/// Matches a season and episode, e.g. `2x01`, `S01E03` or `S01-02xE001-150`.
+///
+/// `^S?(\d{1,2})(?:-S?(\d{1,2}))?(?:x|[ ._-x]?EP?)(\d{1,4})(?:-(?:EP?)?(\d{1,4}))?(?:[vV](\d))?$`
+///
+/// Note that `_-x` in the character class is a range, i.e. it contains `_` to `x` but not `-`.
+pub(crate) fn match_season_and_episode(s: &str) -> Option<SeasonAndEpisode<'_>> {
+    let rest = s.strip_prefix('S').unwrap_or(s);
+    let (season, rest) = take_digits(rest, 1, 2)?;
+    let (season_end, rest) = match rest.strip_prefix('-') {
+        Some(rest) => {
+            let rest = rest.strip_prefix('S').unwrap_or(rest);
+            let (end, rest) = take_digits(rest, 1, 2)?;
+            (Some(end), rest)
+        }
+        None => (None, rest),
+    };
+
+    // Everything after the separator `(?:x|[ ._-x]?EP?)`
+    fn episode(rest: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+        let (episode, rest) = take_digits(rest, 1, 4)?;
+        let (episode_end, rest) = match rest.strip_prefix('-') {
+            Some(rest) => {
+                let rest = match rest.strip_prefix('E') {
+                    Some(rest) => rest.strip_prefix('P').unwrap_or(rest),
+                    None => rest,
+                };
+                let (end, rest) = take_digits(rest, 1, 4)?;
+                (Some(end), rest)
+            }
+            None => (None, rest),
+        };
+        Some((episode, episode_end, match_version(rest)?))
+    }
+
+    let after_separator = rest.strip_prefix('x').and_then(episode).or_else(|| {
+        let rest = match rest.strip_prefix(|ch| matches!(ch, ' ' | '.' | '_'..='x')) {
+            Some(after) if after.starts_with('E') => after,
+            _ => rest,
+        };
+        let rest = rest.strip_prefix('E')?;
+        episode(rest.strip_prefix('P').unwrap_or(rest))
+    });
+
+    let (episode, episode_end, version) = after_separator?;
+    Some(SeasonAndEpisode {
+        season,
+        season_end,
+        episode,
+        episode_end,
+        version,
+    })
+}
+
+/// Finds an episode after a number sign, e.g. `#01` or `#02-03v2`.
+///
+/// Returns the episode, the end of the range and the release version, if any.
+///
+/// `[#＃](\d{1,4})(?:[-~&+](\d{1,4}))?(?:[vV](\d))?`
+pub(crate) fn find_number_sign_episode(s: &str) -> Option<(&str, Option<&str>, Option<&str>)> {
+    s.char_indices()
+        .filter(|&(_, ch)| ch == '#' || ch == '＃')
+        .find_map(|(index, ch)| {
+            let (episode, rest) = take_digits(&s[(index + ch.len_utf8())..], 1, 4)?;
+            let (end, rest) = match rest
+                .strip_prefix(['-', '~', '&', '+'])
+                .and_then(|rest| take_digits(rest, 1, 4))
+            {
+                Some((end, rest)) => (Some(end), rest),
+                None => (None, rest),
+            };
+            let version = rest
+                .strip_prefix(['v', 'V'])
+                .and_then(|rest| take_digits(rest, 1, 1))
+                .map(|(version, _)| version);
+            Some((episode, end, version))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_digits() {
+        assert!(is_decimal_digit('0'));
+        assert!(is_decimal_digit('9'));
+        assert!(is_decimal_digit('１'));
+        assert!(is_decimal_digit('٣'));
+        assert!(!is_decimal_digit('/'));
+        assert!(!is_decimal_digit(':'));
+        assert!(!is_decimal_digit('²'));
+        assert!(!is_decimal_digit('一'));
+    }
+
+    #[test]
+    fn test_video_resolution() {
+        for valid in [
+            "480p",
+            "1080i",
+            "1080p",
+            "1920x1080",
+            "1280X720p",
+            "1920×1080",
+            "１０８０p",
+        ] {
+            assert!(is_video_resolution(valid), "{valid}");
+        }
+        for invalid in [
+            "80p",
+            "10800p",
+            "1080",
+            "1080P",
+            "1920x",
+            "1920x10",
+            "1920x1080pp",
+            "x1080",
+        ] {
+            assert!(!is_video_resolution(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_episode_prefix() {
+        assert_eq!(match_episode_prefix("E01"), Some(("01", None)));
+        assert_eq!(match_episode_prefix("EP01v2"), Some(("01", Some("2"))));
+        assert_eq!(match_episode_prefix("ep07.5"), Some(("07.5", None)));
+        assert_eq!(match_episode_prefix("eP1234V3"), Some(("1234", Some("3"))));
+        assert_eq!(match_episode_prefix("Eps12"), Some(("12", None)));
+        assert_eq!(match_episode_prefix("e01"), None);
+        assert_eq!(match_episode_prefix("eps12"), None);
+        assert_eq!(match_episode_prefix("E12345"), None);
+        assert_eq!(match_episode_prefix("E07.55"), None);
+        assert_eq!(match_episode_prefix("E01v"), None);
+        assert_eq!(match_episode_prefix("E01v23"), None);
+    }
+
+    #[test]
+    fn test_season_and_episode() {
+        type Parts<'a> = (
+            &'a str,
+            Option<&'a str>,
+            &'a str,
+            Option<&'a str>,
+            Option<&'a str>,
+        );
+        fn parts(s: &str) -> Option<Parts<'_>> {
+            match_season_and_episode(s)
+                .map(|m| (m.season, m.season_end, m.episode, m.episode_end, m.version))
+        }
+        assert_eq!(parts("2x01"), Some(("2", None, "01", None, None)));
+        assert_eq!(parts("S01E03"), Some(("01", None, "03", None, None)));
+        assert_eq!(
+            parts("S01EP03v2"),
+            Some(("01", None, "03", None, Some("2")))
+        );
+        assert_eq!(parts("S01.E03"), Some(("01", None, "03", None, None)));
+        assert_eq!(parts("S01xE03"), Some(("01", None, "03", None, None)));
+        assert_eq!(parts("S1aE3"), Some(("1", None, "3", None, None)));
+        assert_eq!(
+            parts("S01-02xE001-150"),
+            Some(("01", Some("02"), "001", Some("150"), None))
+        );
+        assert_eq!(
+            parts("S01-S02E01-EP12"),
+            Some(("01", Some("02"), "01", Some("12"), None))
+        );
+        assert_eq!(parts("s01e03"), None);
+        assert_eq!(parts("S01-E03"), None);
+        assert_eq!(parts("S01yE03"), None);
+        assert_eq!(parts("S001E03"), None);
+        assert_eq!(parts("S01E12345"), None);
+        assert_eq!(parts("S01E03-"), None);
+    }
+
+    #[test]
+    fn test_number_sign_episode() {
+        assert_eq!(find_number_sign_episode("#01"), Some(("01", None, None)));
+        assert_eq!(
+            find_number_sign_episode("#02-03v2"),
+            Some(("02", Some("03"), Some("2")))
+        );
+        assert_eq!(
+            find_number_sign_episode("＃5~6"),
+            Some(("5", Some("6"), None))
+        );
+        assert_eq!(
+            find_number_sign_episode("No#12345"),
+            Some(("1234", None, None))
+        );
+        assert_eq!(find_number_sign_episode("#a#7v"), Some(("7", None, None)));
+        assert_eq!(find_number_sign_episode("#01-"), Some(("01", None, None)));
+        assert_eq!(find_number_sign_episode("#"), None);
+        assert_eq!(find_number_sign_episode("01"), None);
+    }
+}
@@ -0,0 +1,292 @@
+//! The Python bindings, built as a wheel with `maturin`.
+
+use alloc::{format, string::String, vec::Vec};
+
+use pyo3::{exceptions::PyTypeError, prelude::*, types::PyDict};
+
+use crate::{
+    element::{Element, ElementKind, ElementObject},
+    format::{TemplateFields, FIELDS},
+    options::Options,
+    parser::Parser,
+};
+
+/// The names of the builder methods of [`Options`], which are also the keyword arguments
+const OPTIONS: [&str; 10] = [
+    "episodes",
+    "episode_titles",
+    "file_checksums",
+    "file_extensions",
+    "release_groups",
+    "seasons",
+    "titles",
+    "video_resolutions",
+    "years",
+    "dates",
+];
+
+fn is_enabled(options: Options, name: &str) -> bool {
+    match name {
+        "episodes" => options.parse_episode(),
+        "episode_titles" => options.parse_episode_title(),
+        "file_checksums" => options.parse_file_checksum(),
+        "file_extensions" => options.parse_file_extension(),
+        "release_groups" => options.parse_release_group(),
+        "seasons" => options.parse_season(),
+        "titles" => options.parse_title(),
+        "video_resolutions" => options.parse_video_resolution(),
+        "years" => options.parse_year(),
+        "dates" => options.parse_date(),
+        _ => false,
+    }
+}
+
+/// Applies keyword arguments such as `episode_titles=False` on top of the options
+fn apply_kwargs(mut options: Options, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Options> {
+    for (key, value) in kwargs.into_iter().flatten() {
+        let key = key.extract::<String>()?;
+        options = options
+            .toggle_by_name(&key, value.extract()?)
+            .ok_or_else(|| PyTypeError::new_err(format!("unexpected keyword argument '{key}'")))?;
+    }
+    Ok(options)
+}
+
+/// Returns the name of the kind, which is the same as its key in [`ElementObject`]
+const fn kind_name(kind: ElementKind) -> &'static str {
+    match kind {
+        ElementKind::AudioTerm => "audio_term",
+        ElementKind::DeviceCompatibility => "device_compatibility",
+        ElementKind::Episode => "episode",
+        ElementKind::EpisodeTitle => "episode_title",
+        ElementKind::EpisodeAlt => "episode_alt",
+        ElementKind::FileChecksum => "file_checksum",
+        ElementKind::FileExtension => "file_extension",
+        ElementKind::Language => "language",
+        ElementKind::Other => "other",
+        ElementKind::ReleaseGroup => "release_group",
+        ElementKind::ReleaseInformation => "release_information",
+        ElementKind::ReleaseVersion => "release_version",
+        ElementKind::Season => "season",
+        ElementKind::Source => "source",
+        ElementKind::Subtitles => "subtitles",
+        ElementKind::Title => "title",
+        ElementKind::Type => "type",
+        ElementKind::VideoResolution => "video_resolution",
+        ElementKind::VideoTerm => "video_term",
+        ElementKind::Volume => "volume",
+        ElementKind::Year => "year",
+        ElementKind::Date => "date",
+    }
+}
+
+/// Converts the byte span of the element into character indices of the Python string
+fn char_span(input: &str, element: &Element<'_>) -> (usize, usize) {
+    let start = input[..element.span.start].chars().count();
+    let end = start + input[element.span.clone()].chars().count();
+    (start, end)
+}
+
+/// Options relating to the parser.
+///
+/// Everything is enabled by default. The keyword arguments are the names of the
+/// builder methods, e.g. `Options(episode_titles=False)`.
+#[pyclass(name = "Options", module = "anitomy", frozen, eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PyOptions(Options);
+
+#[pymethods]
+impl PyOptions {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        apply_kwargs(Options::default(), kwargs).map(Self)
+    }
+
+    /// Returns new options with parsing episodes toggled
+    fn episodes(&self, toggle: bool) -> Self {
+        Self(self.0.episodes(toggle))
+    }
+
+    /// Returns new options with parsing episode titles toggled
+    fn episode_titles(&self, toggle: bool) -> Self {
+        Self(self.0.episode_titles(toggle))
+    }
+
+    /// Returns new options with parsing file checksums toggled
+    fn file_checksums(&self, toggle: bool) -> Self {
+        Self(self.0.file_checksums(toggle))
+    }
+
+    /// Returns new options with parsing file extensions toggled
+    fn file_extensions(&self, toggle: bool) -> Self {
+        Self(self.0.file_extensions(toggle))
+    }
+
+    /// Returns new options with parsing release groups toggled
+    fn release_groups(&self, toggle: bool) -> Self {
+        Self(self.0.release_groups(toggle))
+    }
+
+    /// Returns new options with parsing seasons toggled
+    fn seasons(&self, toggle: bool) -> Self {
+        Self(self.0.seasons(toggle))
+    }
+
+    /// Returns new options with parsing titles toggled
+    fn titles(&self, toggle: bool) -> Self {
+        Self(self.0.titles(toggle))
+    }
+
+    /// Returns new options with parsing video resolutions toggled
+    fn video_resolutions(&self, toggle: bool) -> Self {
+        Self(self.0.video_resolutions(toggle))
+    }
+
+    /// Returns new options with parsing years toggled
+    fn years(&self, toggle: bool) -> Self {
+        Self(self.0.years(toggle))
+    }
+
+    /// Returns new options with parsing dates toggled
+    fn dates(&self, toggle: bool) -> Self {
+        Self(self.0.dates(toggle))
+    }
+
+    fn parse_episode(&self) -> bool {
+        self.0.parse_episode()
+    }
+
+    fn parse_episode_title(&self) -> bool {
+        self.0.parse_episode_title()
+    }
+
+    fn parse_file_checksum(&self) -> bool {
+        self.0.parse_file_checksum()
+    }
+
+    fn parse_file_extension(&self) -> bool {
+        self.0.parse_file_extension()
+    }
+
+    fn parse_release_group(&self) -> bool {
+        self.0.parse_release_group()
+    }
+
+    fn parse_season(&self) -> bool {
+        self.0.parse_season()
+    }
+
+    fn parse_title(&self) -> bool {
+        self.0.parse_title()
+    }
+
+    fn parse_video_resolution(&self) -> bool {
+        self.0.parse_video_resolution()
+    }
+
+    fn parse_year(&self) -> bool {
+        self.0.parse_year()
+    }
+
+    fn parse_date(&self) -> bool {
+        self.0.parse_date()
+    }
+
+    fn __repr__(&self) -> String {
+        let args = OPTIONS
+            .iter()
+            .map(|name| {
+                let value = if is_enabled(self.0, name) {
+                    "True"
+                } else {
+                    "False"
+                };
+                format!("{name}={value}")
+            })
+            .collect::<Vec<_>>();
+        format!("Options({})", args.join(", "))
+    }
+}
+
+fn parse_with<'a>(
+    name: &'a str,
+    options: Option<PyOptions>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<Element<'a>>> {
+    let options = apply_kwargs(options.map(|x| x.0).unwrap_or_default(), kwargs)?;
+    Ok(Parser::new().options(options).parse(name))
+}
+
+/// Parses a name into a list of `(kind, value, (start, end))` tuples.
+///
+/// The kinds are the keys used by `parse_object` and the spans are indices into the name.
+/// The options can be given as an `Options` object, keyword arguments or both, e.g.
+/// `parse(name, episode_titles=False)`.
+#[pyfunction]
+#[pyo3(signature = (name, options = None, **kwargs))]
+fn parse(
+    name: &str,
+    options: Option<PyOptions>,
+    kwargs: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Vec<(&'static str, String, (usize, usize))>> {
+    let elements = parse_with(name, options, kwargs)?;
+    Ok(elements
+        .iter()
+        .map(|e| (kind_name(e.kind), e.value().into(), char_span(name, e)))
+        .collect())
+}
+
+/// Parses a name into a dict mirroring `ElementObject`, e.g. `{"title": ..., "episode": ...}`.
+///
+/// Kinds that weren't found are missing from the dict. If a kind is found more than once
+/// then the last one is used. The options are the same as `parse`.
+#[pyfunction]
+#[pyo3(signature = (name, options = None, **kwargs))]
+fn parse_object<'py>(
+    py: Python<'py>,
+    name: &str,
+    options: Option<PyOptions>,
+    kwargs: Option<&Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let elements = parse_with(name, options, kwargs)?;
+    let object = elements.iter().collect::<ElementObject<'_>>();
+    let dict = PyDict::new(py);
+    for field in FIELDS {
+        if let Some(value) = object.field(field) {
+            dict.set_item(field, &*value)?;
+        }
+    }
+    Ok(dict)
+}
+
+#[pymodule]
+#[pyo3(name = "anitomy")]
+fn anitomy_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOptions>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_object, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_helpers() {
+        let input = "【Group】 タイトル - 01.mkv";
+        let elements = Parser::new().parse(input);
+        let title = elements.iter().find(|e| e.kind == ElementKind::Title);
+        assert_eq!(title.map(|e| char_span(input, e)), Some((8, 12)));
+        assert!(elements.iter().all(|e| FIELDS.contains(&kind_name(e.kind))));
+
+        let mut options = Options::default();
+        for name in OPTIONS {
+            assert!(is_enabled(options, name));
+            options = options.toggle_by_name(name, false).unwrap();
+            assert!(!is_enabled(options, name));
+        }
+        assert_eq!(options.toggle_by_name("unknown", true), None);
+    }
+}
@@ -0,0 +1,396 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{
+    element::{Element, ElementKind},
+    episode::EpisodeSet,
+    utils::parse_number,
+};
+
+/// A number or an inclusive range of numbers, e.g. `S01` or `S01-02`.
+///
+/// Episodes use the more descriptive [`EpisodeSet`] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum NumberRange {
+    /// A single number
+    Single(u16),
+    /// An inclusive range of numbers
+    Range { start: u16, end: u16 },
+}
+
+impl NumberRange {
+    /// Returns the first number in the range
+    pub const fn start(&self) -> u16 {
+        match self {
+            Self::Single(x) => *x,
+            Self::Range { start, .. } => *start,
+        }
+    }
+
+    /// Returns the last number in the range
+    pub const fn end(&self) -> u16 {
+        match self {
+            Self::Single(x) => *x,
+            Self::Range { end, .. } => *end,
+        }
+    }
+
+    /// Returns a bool indicating whether the number is within this range
+    pub const fn contains(&self, number: u16) -> bool {
+        self.start() <= number && number <= self.end()
+    }
+}
+
+/// A calendar date, e.g. `2012.05.06`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Parses a date in `YYYY-MM-DD` or `YYYY.MM.DD` format
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.split(['-', '.']);
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+}
+
+/// A video resolution, e.g. `1080p` or `1920x1080`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Resolution {
+    /// The width of the video, if known.
+    ///
+    /// This is only known when the resolution is given in `WIDTHxHEIGHT` format.
+    pub width: Option<u16>,
+    /// The height of the video
+    pub height: u16,
+}
+
+impl Resolution {
+    /// Parses a resolution in `1080p`, `1080i`, `1920x1080`, `1080` or `4K` format
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("4k") {
+            return Some(Self {
+                width: None,
+                height: 2160,
+            });
+        }
+
+        let s = s.strip_suffix(['p', 'P', 'i', 'I']).unwrap_or(s);
+        match s.split_once(['x', 'X', '×']) {
+            Some((width, height)) => Some(Self {
+                width: Some(width.parse().ok()?),
+                height: height.parse().ok()?,
+            }),
+            None => Some(Self {
+                width: None,
+                height: s.parse().ok()?,
+            }),
+        }
+    }
+}
+
+/// A typed representation of a parsed release.
+///
+/// Unlike [`ElementObject`](crate::ElementObject), values that represent numbers are
+/// converted into their respective types. Values that could not be converted are
+/// left as `None`. Keyword based elements that are commonly found more than once
+/// (e.g. audio and video terms) are collected in order of appearance.
+///
+/// This is created from a slice of [`Element`] objects, e.g.
+///
+/// ```
+/// let elements = anitomy::parse("[TaigaSubs]_Toradora!_(2008)_-_01v2_[1280x720][1234ABCD].mkv");
+/// let release = elements.iter().collect::<anitomy::ParsedRelease>();
+/// assert_eq!(release.year, Some(2008));
+/// assert_eq!(release.release_version, Some(2));
+/// assert_eq!(release.file_checksum, Some(0x1234ABCD));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedRelease<'a> {
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub audio_term: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub device_compatibility: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub episode: Option<EpisodeSet>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub episode_alt: Option<EpisodeSet>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub episode_title: Option<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub file_checksum: Option<u32>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub file_extension: Option<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub language: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub other: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub release_group: Option<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub release_information: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub release_version: Option<u8>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub season: Option<NumberRange>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub source: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub subtitles: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Option::is_none")
+    )]
+    pub title: Option<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "type",
+            borrow,
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub kind: Option<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub video_resolution: Option<Resolution>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(borrow, default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub video_term: Vec<Cow<'a, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub volume: Option<NumberRange>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub year: Option<u16>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub date: Option<Date>,
+}
+
+/// Adds a number to the range.
+///
+/// A number after the first is only used if it ends a range (e.g. `S01-02`), since a list
+/// of numbers (e.g. `S01&S03`) can't be represented as a range.
+fn add_number(range: &mut Option<NumberRange>, value: &str, is_range_end: bool) {
+    let Some(number) = parse_number(value).and_then(|x| u16::try_from(x).ok()) else {
+        return;
+    };
+    match *range {
+        None => *range = Some(NumberRange::Single(number)),
+        Some(NumberRange::Single(start)) if is_range_end && start < number => {
+            *range = Some(NumberRange::Range { start, end: number });
+        }
+        Some(_) => {}
+    }
+}
+
+impl<'a> ParsedRelease<'a> {
+    fn push(
+        &mut self,
+        kind: ElementKind,
+        value: Cow<'a, str>,
+        is_range_end: bool,
+        previous: Option<ElementKind>,
+    ) {
+        match kind {
+            ElementKind::AudioTerm => self.audio_term.push(value),
+            ElementKind::DeviceCompatibility => self.device_compatibility.push(value),
+            ElementKind::Episode => EpisodeSet::push_value(&mut self.episode, &value, is_range_end),
+            ElementKind::EpisodeAlt => {
+                EpisodeSet::push_value(&mut self.episode_alt, &value, is_range_end)
+            }
+            ElementKind::EpisodeTitle => self.episode_title = Some(value),
+            ElementKind::FileChecksum => {
+                self.file_checksum = u32::from_str_radix(&value, 16).ok();
+            }
+            ElementKind::FileExtension => self.file_extension = Some(value),
+            ElementKind::Language => self.language.push(value),
+            ElementKind::Other => self.other.push(value),
+            ElementKind::ReleaseGroup => self.release_group = Some(value),
+            ElementKind::ReleaseInformation => self.release_information.push(value),
+            ElementKind::ReleaseVersion => {
+                match previous {
+                    Some(ElementKind::Episode) => {
+                        EpisodeSet::set_release_version(&mut self.episode, &value)
+                    }
+                    Some(ElementKind::EpisodeAlt) => {
+                        EpisodeSet::set_release_version(&mut self.episode_alt, &value)
+                    }
+                    _ => {}
+                }
+                self.release_version = parse_number(&value).and_then(|x| x.try_into().ok());
+            }
+            ElementKind::Season => add_number(&mut self.season, &value, is_range_end),
+            ElementKind::Source => self.source.push(value),
+            ElementKind::Subtitles => self.subtitles.push(value),
+            ElementKind::Title => self.title = Some(value),
+            ElementKind::Type => self.kind = Some(value),
+            ElementKind::VideoResolution => self.video_resolution = Resolution::parse(&value),
+            ElementKind::VideoTerm => self.video_term.push(value),
+            ElementKind::Volume => add_number(&mut self.volume, &value, is_range_end),
+            ElementKind::Year => self.year = parse_number(&value).and_then(|x| x.try_into().ok()),
+            ElementKind::Date => self.date = Date::parse(&value),
+        }
+    }
+}
+
+impl<'a, 'b: 'a> FromIterator<&'b Element<'a>> for ParsedRelease<'a> {
+    fn from_iter<T: IntoIterator<Item = &'b Element<'a>>>(iter: T) -> Self {
+        use core::borrow::Borrow;
+        let mut release = Self::default();
+        let mut previous = None;
+        for element in iter {
+            let value = Cow::Borrowed(element.value.borrow());
+            release.push(element.kind, value, element.is_range_end, previous);
+            previous = Some(element.kind);
+        }
+        release
+    }
+}
+
+impl<'a> FromIterator<Element<'a>> for ParsedRelease<'a> {
+    fn from_iter<T: IntoIterator<Item = Element<'a>>>(iter: T) -> Self {
+        let mut release = Self::default();
+        let mut previous = None;
+        for element in iter {
+            release.push(element.kind, element.value, element.is_range_end, previous);
+            previous = Some(element.kind);
+        }
+        release
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::episode::EpisodeNumber;
+
+    use super::*;
+
+    #[test]
+    fn test_resolution() {
+        let cases = [
+            ("1080p", Some((None, 1080))),
+            ("1080i", Some((None, 1080))),
+            ("720", Some((None, 720))),
+            ("1920x1080", Some((Some(1920), 1080))),
+            ("1280×720", Some((Some(1280), 720))),
+            ("4K", Some((None, 2160))),
+            ("HD", None),
+        ];
+        for (input, expected) in cases {
+            let expected = expected.map(|(width, height)| Resolution { width, height });
+            assert_eq!(Resolution::parse(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parsed_release() {
+        let elements = crate::parse(
+            "[Group] Title S01-S02 - 03 (2012.05.06) [1920x1080 AAC FLAC][ABCD1234].mkv",
+        );
+        let release = elements.iter().collect::<ParsedRelease>();
+        assert_eq!(release.title.as_deref(), Some("Title"));
+        assert_eq!(release.release_group.as_deref(), Some("Group"));
+        assert_eq!(
+            release.season,
+            Some(NumberRange::Range { start: 1, end: 2 })
+        );
+        assert_eq!(
+            release.episode,
+            Some(EpisodeSet::Single(EpisodeNumber::new(3)))
+        );
+        assert_eq!(
+            release.date,
+            Some(Date {
+                year: 2012,
+                month: 5,
+                day: 6
+            })
+        );
+        assert_eq!(
+            release.video_resolution,
+            Some(Resolution {
+                width: Some(1920),
+                height: 1080
+            })
+        );
+        assert_eq!(release.audio_term, ["AAC", "FLAC"]);
+        assert_eq!(release.file_checksum, Some(0xABCD1234));
+        assert_eq!(release.file_extension.as_deref(), Some("mkv"));
+
+        // Lists of seasons aren't ranges, only the first one is kept
+        let elements = crate::parse("[Group] Title S01&S03 [1080p].mkv");
+        let release = elements.iter().collect::<ParsedRelease>();
+        assert_eq!(release.season, Some(NumberRange::Single(1)));
+    }
+}
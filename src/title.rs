@@ -0,0 +1,264 @@
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+use uncased::{Uncased, UncasedStr};
+
+use crate::tokenizer::{Token, Tokenizer};
+
+/// A dictionary of known titles.
+///
+/// Known titles are found in the name before any keywords or episodes are parsed, and the
+/// tokens that make up the title are locked so they're never claimed as something else.
+/// This protects titles that contain keywords (e.g. `Tokyo ESP`) or numbers (e.g. `86`
+/// or `Steins;Gate 0`).
+///
+/// Titles are matched case insensitively and regardless of the delimiters and the
+/// punctuation around words, e.g. `Tokyo ESP` matches `Tokyo_ESP` and `tokyo.esp`, and
+/// `Dragon Ball Z: Super Android 13` matches `Dragon.Ball.Z.Super.Android.13`. The title
+/// element is the title as it was added to the dictionary.
+///
+/// ```
+/// use anitomy::{ElementKind, Parser, TitleTable};
+///
+/// let mut titles = TitleTable::new();
+/// titles.insert("Steins;Gate 0");
+///
+/// let parser = Parser::new().titles(titles);
+/// let elements = parser.parse("Steins;Gate 0 - 05 [1080p].mkv");
+/// let find = |kind| elements.iter().find(|e| e.kind() == kind).map(|e| e.value());
+/// assert_eq!(find(ElementKind::Title), Some("Steins;Gate 0"));
+/// assert_eq!(find(ElementKind::Episode), Some("05"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TitleTable {
+    /// The titles along with the words of their non-delimiter tokens
+    entries: BTreeMap<Uncased<'static>, Vec<String>>,
+}
+
+impl TitleTable {
+    /// Creates an empty dictionary
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a title to the dictionary
+    pub fn insert(&mut self, title: &str) {
+        let words = Tokenizer::new(title)
+            .tokens()
+            .into_iter()
+            .filter(|t| t.is_not_delimiter())
+            .map(|t| word(t.value))
+            .filter(|w| !w.is_empty())
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>();
+        if !words.is_empty() {
+            self.entries.insert(Uncased::new(title.to_owned()), words);
+        }
+    }
+
+    /// Removes a title from the dictionary, returning whether it was in the dictionary
+    pub fn remove(&mut self, title: &str) -> bool {
+        self.entries.remove(UncasedStr::new(title)).is_some()
+    }
+
+    /// Returns a bool indicating whether the title is in the dictionary
+    pub fn contains(&self, title: &str) -> bool {
+        self.entries.contains_key(UncasedStr::new(title))
+    }
+
+    /// Returns an iterator over every title in the dictionary
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.entries.keys().map(|title| title.as_str())
+    }
+
+    /// Returns the number of titles in the dictionary
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns a bool indicating whether the dictionary has no titles
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the end of the tokens starting at `index` that match the given words
+    fn match_at(tokens: &[Token<'_>], index: usize, words: &[String]) -> Option<usize> {
+        let mut current = index;
+        for (position, word) in words.iter().enumerate() {
+            // Any delimiters or punctuation are allowed in between words
+            if position != 0 {
+                while tokens.get(current).is_some_and(|t| {
+                    t.is_delimiter() || (t.is_free() && self::word(t.value).is_empty())
+                }) {
+                    current += 1;
+                }
+            }
+            let token = tokens.get(current)?;
+            if token.is_identified() || !self::word(token.value).eq_ignore_ascii_case(word) {
+                return None;
+            }
+            current += 1;
+        }
+        Some(current)
+    }
+
+    /// Returns the indices of the tokens where a title can start.
+    ///
+    /// This is the start of the first unenclosed range (e.g. `[Group] Title - 01`) or, if
+    /// everything is enclosed, the start of every bracket (e.g. `[Group][Title][01]`).
+    fn anchors(tokens: &[Token<'_>]) -> Vec<usize> {
+        let first = tokens
+            .iter()
+            .position(|t| t.is_free() && t.is_not_delimiter() && !t.is_enclosed);
+        if let Some(index) = first {
+            return vec![index];
+        }
+        (0..tokens.len())
+            .filter(|&index| tokens[index].is_open_bracket())
+            .filter_map(|index| {
+                let start = index
+                    + 1
+                    + tokens[index + 1..]
+                        .iter()
+                        .take_while(|t| t.is_delimiter())
+                        .count();
+                tokens.get(start)?.is_free().then_some(start)
+            })
+            .collect()
+    }
+
+    /// Returns the end of the longest title that starts at the given index along with the title
+    fn longest_at(&self, tokens: &[Token<'_>], index: usize) -> Option<(usize, &str)> {
+        self.entries
+            .iter()
+            .filter_map(|(title, words)| {
+                Some((Self::match_at(tokens, index, words)?, title.as_str()))
+            })
+            .max_by_key(|(end, _)| *end)
+    }
+
+    /// Finds the range of tokens of the known title where a title would be, preferring
+    /// the longest one.
+    ///
+    /// Titles elsewhere in the name are ignored, e.g. a known title `86` is not the title
+    /// of `One Piece - 86`.
+    pub(crate) fn find(&self, tokens: &[Token<'_>]) -> Option<(Range<usize>, &str)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        Self::anchors(tokens).into_iter().find_map(|index| {
+            let (end, title) = self.longest_at(tokens, index)?;
+            Some((index..end, title))
+        })
+    }
+
+    /// Finds the range of tokens of the first known title anywhere in the name, preferring
+    /// the longest one.
+    ///
+    /// This is only used for titles that a batch of names agrees on, where the title is
+    /// already known to be right.
+    pub(crate) fn find_anywhere(&self, tokens: &[Token<'_>]) -> Option<Range<usize>> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        (0..tokens.len())
+            .filter(|&index| tokens[index].is_free())
+            .find_map(|index| self.longest_at(tokens, index).map(|(end, _)| index..end))
+    }
+}
+
+/// Returns the part of a token that is compared to the words of a title, i.e. without
+/// the punctuation around it (e.g. the `Z` in `Z:`)
+fn word(value: &str) -> &str {
+    value.trim_matches(|ch: char| ch.is_ascii_punctuation())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ElementKind, Parser};
+
+    #[test]
+    fn test_known_titles() {
+        let mut parser = Parser::new();
+        let titles = parser.titles_mut();
+        titles.insert("Tokyo ESP");
+        titles.insert("86");
+        titles.insert("Dragon Ball Z: Super Android 13");
+        titles.insert("Steins;Gate");
+        titles.insert("Steins;Gate 0");
+        assert!(titles.contains("tokyo esp"));
+        assert_eq!(titles.len(), 5);
+
+        let cases = [
+            ("[Group] Tokyo_ESP_-_01_[720p].mkv", "Tokyo ESP", Some("01")),
+            ("[Group] tokyo.esp - 02 [720p].mkv", "Tokyo ESP", Some("02")),
+            ("[Group] 86 - 03 [1080p].mkv", "86", Some("03")),
+            (
+                "Dragon.Ball.Z.Super.Android.13.mkv",
+                "Dragon Ball Z: Super Android 13",
+                None,
+            ),
+            (
+                "[Group] Dragon Ball Z - Super Android 13 [720p].mkv",
+                "Dragon Ball Z: Super Android 13",
+                None,
+            ),
+            (
+                "Dragon Ball Z : Super Android 13 [720p].mkv",
+                "Dragon Ball Z: Super Android 13",
+                None,
+            ),
+            (
+                "Steins;Gate 0 - 05 [1080p].mkv",
+                "Steins;Gate 0",
+                Some("05"),
+            ),
+        ];
+        for (input, title, episode) in cases {
+            let elements = parser.parse(input);
+            let find = |kind| {
+                elements
+                    .iter()
+                    .find(|e| e.kind() == kind)
+                    .map(|e| e.value())
+            };
+            assert_eq!(find(ElementKind::Title), Some(title), "{input}");
+            assert_eq!(find(ElementKind::Episode), episode, "{input}");
+            assert_eq!(find(ElementKind::Language), None, "{input}");
+            assert_eq!(find(ElementKind::DeviceCompatibility), None, "{input}");
+        }
+
+        // Known titles are only found where a title would be
+        let cases = [
+            ("[Group] One Piece - 86 [720p].mkv", "One Piece", Some("86")),
+            (
+                "[Group] Other Show - 01 - Tokyo ESP [720p].mkv",
+                "Other Show",
+                Some("01"),
+            ),
+            ("[Group][Tokyo ESP][01][720p].mkv", "Tokyo ESP", Some("01")),
+        ];
+        for (input, title, episode) in cases {
+            let elements = parser.parse(input);
+            let find = |kind| {
+                elements
+                    .iter()
+                    .find(|e| e.kind() == kind)
+                    .map(|e| e.value())
+            };
+            assert_eq!(find(ElementKind::Title), Some(title), "{input}");
+            assert_eq!(find(ElementKind::Episode), episode, "{input}");
+        }
+        let elements = parser.parse("[Group] Other Show - 01 - Tokyo ESP [720p].mkv");
+        assert!(elements
+            .iter()
+            .all(|e| e.kind() != ElementKind::EpisodeTitle || e.value() == "Tokyo ESP"));
+
+        assert!(parser.titles_mut().remove("86"));
+        assert!(!parser.titles_mut().contains("86"));
+    }
+}
@@ -1,29 +1,42 @@
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
 use uncased::UncasedStr;
 
 use crate::{
-    keyword::{Keyword, KEYWORDS},
+    keyword::{Keyword, KeywordKind, KeywordTable},
     utils::get_pair_mut,
 };
 
+/// The kind of token that the input was split into
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) enum TokenKind {
+pub enum TokenKind {
+    /// An opening bracket, e.g. `(`, `[` or `「`
     OpenBracket,
+    /// A closing bracket, e.g. `)`, `]` or `」`
     CloseBracket,
+    /// A delimiter between words, e.g. a space, `_` or `.`
     Delimiter,
+    /// A known keyword, e.g. `x264` or `BD`
     Keyword,
+    /// Any other text
     Text,
+    /// Text made up of only ASCII digits
     Number,
-    Invalid,
 }
 
+/// A token of the input as seen by the parser.
+///
+/// Tokens are created by [`tokenize`](crate::tokenize).
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct Token<'a> {
+pub struct Token<'a> {
     pub(crate) kind: TokenKind,
     pub(crate) value: &'a str,
     pub(crate) keyword: Option<Keyword>,
     pub(crate) unknown: bool,
     pub(crate) is_enclosed: bool,
     pub(crate) position: usize,
+    pub(crate) offset: usize,
 }
 
 impl<'a> Token<'a> {
@@ -35,6 +48,7 @@ impl<'a> Token<'a> {
             unknown: true,
             is_enclosed: false,
             position: 0,
+            offset: 0,
         }
     }
 
@@ -46,6 +60,7 @@ impl<'a> Token<'a> {
             unknown: true,
             is_enclosed: false,
             position: 0,
+            offset: 0,
         }
     }
 
@@ -57,6 +72,7 @@ impl<'a> Token<'a> {
             unknown: true,
             is_enclosed,
             position: 0,
+            offset: 0,
         }
     }
 
@@ -68,6 +84,7 @@ impl<'a> Token<'a> {
             unknown: true,
             is_enclosed,
             position: 0,
+            offset: 0,
         }
     }
 
@@ -79,9 +96,43 @@ impl<'a> Token<'a> {
             unknown: true,
             is_enclosed,
             position: 0,
+            offset: 0,
         }
     }
 
+    /// Returns the kind of token
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// Returns the value of the token
+    pub fn value(&self) -> &'a str {
+        self.value
+    }
+
+    /// Returns the byte range of this token in the original input
+    pub fn span(&self) -> Range<usize> {
+        self.offset..self.offset + self.value.len()
+    }
+
+    /// Returns a bool indicating whether the token is enclosed in brackets
+    pub fn is_enclosed(&self) -> bool {
+        self.is_enclosed
+    }
+
+    /// Returns the category of the keyword this token matched, if any
+    pub fn keyword(&self) -> Option<KeywordKind> {
+        self.keyword.map(|k| k.kind)
+    }
+
+    /// Returns the byte range of a slice of this token's value in the original input
+    pub(crate) fn span_of(&self, value: &str) -> Range<usize> {
+        let start = value.as_ptr() as usize - self.value.as_ptr() as usize;
+        debug_assert!(start + value.len() <= self.value.len());
+        let start = self.offset + start;
+        start..start + value.len()
+    }
+
     pub(crate) fn mark_known(&mut self) {
         self.unknown = false;
     }
@@ -93,6 +144,10 @@ impl<'a> Token<'a> {
         }
     }
 
+    fn with_offset(self, offset: usize) -> Self {
+        Self { offset, ..self }
+    }
+
     pub(crate) const fn is_identified(&self) -> bool {
         !self.unknown
     }
@@ -253,13 +308,23 @@ fn is_keyword_boundary(s: &str) -> bool {
 }
 
 #[derive(Debug)]
-pub(crate) struct Tokenizer<'a> {
+pub(crate) struct Tokenizer<'a, 'k> {
     input: &'a str,
+    keywords: &'k KeywordTable,
 }
 
-impl<'a> Tokenizer<'a> {
+/// The keyword dictionary used when no other dictionary is given
+static BUILTIN_KEYWORDS: KeywordTable = KeywordTable::new();
+
+impl<'a> Tokenizer<'a, 'static> {
     pub(crate) fn new(s: &'a str) -> Self {
-        Self { input: s }
+        Self::with_keywords(s, &BUILTIN_KEYWORDS)
+    }
+}
+
+impl<'a, 'k> Tokenizer<'a, 'k> {
+    pub(crate) fn with_keywords(s: &'a str, keywords: &'k KeywordTable) -> Self {
+        Self { input: s, keywords }
     }
 
     const fn is_empty(&self) -> bool {
@@ -270,15 +335,10 @@ impl<'a> Tokenizer<'a> {
         let mut key = "";
         for (index, ch) in self.input.char_indices() {
             let prefix = &self.input[0..(index + ch.len_utf8())];
-            if KEYWORDS.contains_key(UncasedStr::new(prefix)) {
+            if self.keywords.get(prefix).is_some() {
                 key = prefix;
             }
-            if KEYWORDS
-                .keys()
-                .filter(|key| key.starts_with(prefix))
-                .count()
-                > 0
-            {
+            if self.keywords.has_prefix(prefix) {
                 continue;
             }
             if key.is_empty() {
@@ -287,7 +347,7 @@ impl<'a> Tokenizer<'a> {
         }
 
         let n = key.len();
-        let keyword = KEYWORDS.get(UncasedStr::new(key)).cloned()?;
+        let keyword = self.keywords.get(key)?;
         let rest = &self.input[n..];
         if keyword.is_bounded() && !is_keyword_boundary(rest) {
             // Allow things like "ED2" or "Season2"
@@ -342,76 +402,80 @@ impl<'a> Tokenizer<'a> {
     }
 
     pub(crate) fn tokens(self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        self.tokens_into(&mut tokens);
+        tokens
+    }
+
+    /// Splits the input into tokens, replacing the contents of the given buffer.
+    pub(crate) fn tokens_into(self, tokens: &mut Vec<Token<'a>>) {
+        // Combined tokens are marked with this position until they're removed
+        const COMBINED: usize = usize::MAX;
+
         let original = self.input;
-        let mut tokens = self.into_iter().collect::<Vec<_>>();
-        let mut start_length = 0;
+        tokens.clear();
+        tokens.extend(self);
 
         // Fix up and combine some tokens (e.g. 1 '.' 2 => '1.2')
         for index in 0..tokens.len() {
-            let token_length = tokens[index].value.len();
             let is_dot = tokens[index].value == ".";
             // Combine tokens separated by a delimiter if they're mostly numbers
             // e.g. 009-1 or 01+02
             if tokens[index].is_delimiter()
                 && tokens[index].value.starts_with(['.', '-', '&', '+', '~'])
             {
-                if let Some((previous, next)) = get_pair_mut(&mut tokens, index - 1, index + 1) {
+                // A leading delimiter has nothing before it to combine with (e.g. -01-02)
+                let Some(before) = index.checked_sub(1) else {
+                    continue;
+                };
+                if let Some((previous, next)) = get_pair_mut(tokens, before, index + 1) {
+                    // A token that is already a part of a combined token is left alone,
+                    // e.g. 01+02+03 becomes 01+02 and 03 rather than overlapping tokens
+                    let is_combined = previous.position == COMBINED;
                     // Handle cases like No.N as well as 1.11
-                    if (previous.is_mostly_numbers() && next.is_mostly_numbers())
-                        || (is_dot
-                            && previous.is_text()
-                            && previous.value == UncasedStr::new("No")
-                            && next.is_number())
+                    if !is_combined
+                        && ((previous.is_mostly_numbers() && next.is_mostly_numbers())
+                            || (is_dot
+                                && previous.is_text()
+                                && previous.value == UncasedStr::new("No")
+                                && next.is_number()))
                     {
-                        previous.kind = TokenKind::Invalid;
-                        next.kind = TokenKind::Invalid;
-                        let start = start_length - previous.value.len();
-                        let end = start_length + token_length + next.value.len();
+                        previous.position = COMBINED;
+                        next.position = COMBINED;
+                        let start = previous.offset;
+                        let end = next.offset + next.value.len();
                         // Create a new combined token anchored by the middle delimiter
                         tokens[index].kind = TokenKind::Text;
                         tokens[index].value = &original[start..end];
+                        tokens[index].offset = start;
                     }
                 }
             }
-            start_length += token_length;
         }
 
-        tokens.retain(|t| t.kind != TokenKind::Invalid);
+        tokens.retain(|t| t.position != COMBINED);
         for (index, token) in tokens.iter_mut().enumerate() {
             token.position = index;
         }
-        tokens
     }
 }
 
-pub(crate) struct TokenIterator<'a> {
-    tokens: Tokenizer<'a>,
+pub(crate) struct TokenIterator<'a, 'k> {
+    tokens: Tokenizer<'a, 'k>,
     bracket_level: usize,
+    length: usize,
 }
 
-impl<'a> TokenIterator<'a> {
-    pub(crate) fn new(tokens: Tokenizer<'a>) -> Self {
+impl<'a, 'k> TokenIterator<'a, 'k> {
+    pub(crate) fn new(tokens: Tokenizer<'a, 'k>) -> Self {
         Self {
+            length: tokens.input.len(),
             tokens,
             bracket_level: 0,
         }
     }
-}
-
-impl<'a> IntoIterator for Tokenizer<'a> {
-    type Item = Token<'a>;
-
-    type IntoIter = TokenIterator<'a>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        TokenIterator::new(self)
-    }
-}
-
-impl<'a> Iterator for TokenIterator<'a> {
-    type Item = Token<'a>;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_token(&mut self) -> Option<Token<'a>> {
         if self.tokens.is_empty() {
             return None;
         }
@@ -448,6 +512,25 @@ impl<'a> Iterator for TokenIterator<'a> {
     }
 }
 
+impl<'a, 'k> IntoIterator for Tokenizer<'a, 'k> {
+    type Item = Token<'a>;
+
+    type IntoIter = TokenIterator<'a, 'k>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TokenIterator::new(self)
+    }
+}
+
+impl<'a, 'k> Iterator for TokenIterator<'a, 'k> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let offset = self.length - self.tokens.input.len();
+        self.next_token().map(|t| t.with_offset(offset))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum KeepDelimiters {
     Yes,
@@ -459,14 +542,15 @@ pub(crate) fn combine_tokens(tokens: &[Token<'_>], keep: KeepDelimiters) -> Stri
     let delimiters = tokens
         .iter()
         .filter(|t| t.is_delimiter())
-        .filter_map(|t| t.value.chars().next())
-        .collect::<Vec<_>>();
-    let has_single_delimiter = delimiters
-        .first()
-        .map(|f| delimiters.iter().all(|x| x == f))
-        .unwrap_or(false);
-    let has_spaces = delimiters.iter().copied().any(is_space);
-    let has_underscores = delimiters.iter().any(|&x| x == '_');
+        .filter_map(|t| t.value.chars().next());
+    let has_single_delimiter = {
+        let mut rest = delimiters.clone();
+        rest.next()
+            .map(|first| rest.all(|x| x == first))
+            .unwrap_or(false)
+    };
+    let has_spaces = delimiters.clone().any(is_space);
+    let has_underscores = delimiters.clone().any(|ch| ch == '_');
 
     let is_transformable_delimiter = |token: &Token<'_>| {
         if keep == KeepDelimiters::Yes {
@@ -524,7 +608,7 @@ pub(crate) fn combine_tokens(tokens: &[Token<'_>], keep: KeepDelimiters) -> Stri
         // This is nothing more than just a manual memcpy
         unsafe {
             let v = buffer.as_mut_vec();
-            std::ptr::copy(ptr, v.as_mut_ptr(), len);
+            core::ptr::copy(ptr, v.as_mut_ptr(), len);
             v.set_len(len);
         }
     }
@@ -532,11 +616,28 @@ pub(crate) fn combine_tokens(tokens: &[Token<'_>], keep: KeepDelimiters) -> Stri
     buffer
 }
 
+/// Returns the byte range in the original input covered by the given tokens.
+///
+/// Delimiters at either end of the range are not included.
+pub(crate) fn tokens_span(tokens: &[Token<'_>]) -> Range<usize> {
+    let first = tokens
+        .iter()
+        .find(|t| t.is_not_delimiter())
+        .or(tokens.first());
+    let last = tokens
+        .iter()
+        .rfind(|t| t.is_not_delimiter())
+        .or(tokens.last());
+    match (first, last) {
+        (Some(first), Some(last)) => first.offset..last.span().end,
+        _ => 0..0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::keyword::KeywordKind;
-
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_tokenizer_toradora() {
@@ -619,7 +720,7 @@ mod tests {
             Token::delimiter("_", false),
             Token::delimiter("-", false),
             Token::delimiter("_", false),
-            Token::from_keyword("THORA", Keyword::new(KeywordKind::ReleaseGroup), false),
+            Token::text("THORA", TokenKind::Text, false),
             Token::delimiter(".", false),
             Token::from_keyword("mkv", Keyword::new(KeywordKind::FileExtension), false),
         ];
@@ -630,4 +731,53 @@ mod tests {
             assert_eq!(original.is_enclosed, expected.is_enclosed);
         }
     }
+
+    #[test]
+    fn test_tokenizer_combined() {
+        let s = "S01+S02 01-02";
+        let tokens = Tokenizer::new(s).tokens();
+        let values = tokens
+            .iter()
+            .filter(|t| t.is_not_delimiter())
+            .map(|t| (t.value(), &s[t.span()]))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [("S01+S02", "S01+S02"), ("01-02", "01-02")]);
+        assert!(tokens.iter().enumerate().all(|(i, t)| t.position == i));
+    }
+
+    #[test]
+    fn test_tokenizer_leading_delimiter() {
+        let cases = [
+            ("-01-02 Title", vec!["-", "01-02", " ", "Title"]),
+            ("+01 Title", vec!["+", "01", " ", "Title"]),
+            (".", vec!["."]),
+        ];
+        for (input, expected) in cases {
+            let tokens = Tokenizer::new(input).tokens();
+            let values = tokens.iter().map(|t| t.value).collect::<Vec<_>>();
+            assert_eq!(values, expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_tokenizer_combined_chain() {
+        // Only the first pair of a chain is combined, the same as before tokens had spans
+        let cases = [
+            ("S01+S02+S03", vec!["S01+S02", "+", "S03"]),
+            ("01+02+03", vec!["01+02", "+", "03"]),
+            (
+                "Title 01-02-03-04",
+                vec!["Title", " ", "01-02", "-", "03-04"],
+            ),
+        ];
+        for (input, expected) in cases {
+            let tokens = Tokenizer::new(input).tokens();
+            let values = tokens.iter().map(|t| t.value).collect::<Vec<_>>();
+            assert_eq!(values, expected, "{input}");
+            assert!(
+                tokens.iter().all(|t| input[t.span()] == *t.value),
+                "{input}"
+            );
+        }
+    }
 }
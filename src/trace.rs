@@ -0,0 +1,343 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{element::Element, tokenizer::Token};
+
+/// A rule of the parser that produces elements.
+///
+/// This is reported by [`parse_traced`](crate::parse_traced) for every element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Rule {
+    /// A file extension at the end of the name, e.g. `.mkv`
+    FileExtension,
+    /// A title from the dictionary of known titles
+    KnownTitle,
+    /// A release group from the dictionary of known release groups
+    KnownReleaseGroup,
+    /// An episode number inferred from a batch of related names
+    BatchEpisode,
+    /// A keyword, e.g. `x264` or `BD`
+    Keyword,
+    /// An 8 character hexadecimal checksum, e.g. `ABCD1234`
+    FileChecksum,
+    /// A video resolution, e.g. `1080p` or `1920x1080`
+    VideoResolution,
+    /// A date, e.g. `2012.05.06`
+    Date,
+    /// An enclosed or isolated year, e.g. `(2008)`
+    Year,
+    /// A season, e.g. `Season 2`, `2nd Season` or `S2`
+    Season,
+    /// A volume, e.g. `Vol.01`
+    Volume,
+    /// An episode after an episode keyword, e.g. `Episode 01`
+    EpisodeKeyword,
+    /// An episode with a prefix, e.g. `E01` or `EP01`
+    EpisodePrefix,
+    /// A number followed by another number, e.g. `01 of 24`
+    NumberInNumber,
+    /// An episode with a release version, e.g. `01v2`
+    SingleEpisode,
+    /// A range of episodes, e.g. `01-12`
+    MultiEpisode,
+    /// A season and episode, e.g. `S01E03` or `2x01`
+    SeasonAndEpisode,
+    /// An episode after a type keyword, e.g. `OVA 2`
+    TypeAndEpisode,
+    /// An episode after a number sign, e.g. `#01`
+    NumberSign,
+    /// A Japanese episode counter, e.g. `第01話`
+    JapaneseCounter,
+    /// An episode with an equivalent number, e.g. `01 (176)`
+    EquivalentNumbers,
+    /// A number after a dash, e.g. ` - 08`
+    SeparatedNumber,
+    /// A fractional episode, e.g. `07.5`
+    FractionalEpisode,
+    /// An enclosed number, e.g. `[12]`
+    IsolatedNumber,
+    /// A partial episode, e.g. `4a`
+    PartialEpisode,
+    /// The fallback to the last number in the name
+    LastNumber,
+    /// The first free range of the name
+    Title,
+    /// The first free enclosed range of the name, or the last one after a dash
+    ReleaseGroup,
+    /// The free range of the name after the episode
+    EpisodeTitle,
+}
+
+impl Rule {
+    /// Returns this Rule as a human representable string
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Rule::FileExtension => "File Extension",
+            Rule::KnownTitle => "Known Title",
+            Rule::KnownReleaseGroup => "Known Release Group",
+            Rule::BatchEpisode => "Batch Episode",
+            Rule::Keyword => "Keyword",
+            Rule::FileChecksum => "File Checksum",
+            Rule::VideoResolution => "Video Resolution",
+            Rule::Date => "Date",
+            Rule::Year => "Year",
+            Rule::Season => "Season",
+            Rule::Volume => "Volume",
+            Rule::EpisodeKeyword => "Episode Keyword",
+            Rule::EpisodePrefix => "Episode Prefix",
+            Rule::NumberInNumber => "Number In Number",
+            Rule::SingleEpisode => "Single Episode",
+            Rule::MultiEpisode => "Multi Episode",
+            Rule::SeasonAndEpisode => "Season And Episode",
+            Rule::TypeAndEpisode => "Type And Episode",
+            Rule::NumberSign => "Number Sign",
+            Rule::JapaneseCounter => "Japanese Counter",
+            Rule::EquivalentNumbers => "Equivalent Numbers",
+            Rule::SeparatedNumber => "Separated Number",
+            Rule::FractionalEpisode => "Fractional Episode",
+            Rule::IsolatedNumber => "Isolated Number",
+            Rule::PartialEpisode => "Partial Episode",
+            Rule::LastNumber => "Last Number",
+            Rule::Title => "Title",
+            Rule::ReleaseGroup => "Release Group",
+            Rule::EpisodeTitle => "Episode Title",
+        }
+    }
+
+    /// Whether the rule produces an element for every token it matches, rather than
+    /// a single match made up of all the tokens it consumed
+    const fn is_per_token(&self) -> bool {
+        matches!(
+            self,
+            Rule::Keyword | Rule::VideoResolution | Rule::Season | Rule::Volume
+        )
+    }
+}
+
+/// An element along with the rule that produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedElement<'a> {
+    element: Element<'a>,
+    rule: Rule,
+    tokens: Vec<usize>,
+}
+
+impl<'a> TracedElement<'a> {
+    /// Returns the parsed element
+    pub fn element(&self) -> &Element<'a> {
+        &self.element
+    }
+
+    /// Returns the rule that produced the element
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns the indices of the tokens that the rule consumed to produce the element.
+    ///
+    /// These are indices into [`Trace::tokens`]. Elements that don't consume their tokens
+    /// (e.g. an unenclosed ambiguous keyword) have no tokens.
+    pub fn tokens(&self) -> &[usize] {
+        &self.tokens
+    }
+}
+
+/// A candidate token that a rule considered but rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rejection {
+    rule: Rule,
+    token: usize,
+    reason: &'static str,
+}
+
+impl Rejection {
+    /// Returns the rule that rejected the candidate
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Returns the index of the candidate token in [`Trace::tokens`]
+    pub fn token(&self) -> usize {
+        self.token
+    }
+
+    /// Returns a short description of why the candidate was rejected
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+/// A trace of how a name was parsed.
+///
+/// See [`parse_traced`](crate::parse_traced) for more information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<'a> {
+    tokens: Vec<Token<'a>>,
+    elements: Vec<TracedElement<'a>>,
+    rejected: Vec<Rejection>,
+}
+
+impl<'a> Trace<'a> {
+    /// Returns the tokens of the name
+    pub fn tokens(&self) -> &[Token<'a>] {
+        &self.tokens
+    }
+
+    /// Returns the parsed elements in the same order as [`parse`](crate::parse)
+    pub fn elements(&self) -> &[TracedElement<'a>] {
+        &self.elements
+    }
+
+    /// Returns the candidates that were rejected, in the order they were considered
+    pub fn rejected(&self) -> &[Rejection] {
+        &self.rejected
+    }
+
+    /// Consumes this and returns the parsed elements
+    pub fn into_elements(self) -> Vec<Element<'a>> {
+        self.elements.into_iter().map(|e| e.element).collect()
+    }
+}
+
+/// Formats the trace as a human readable report, one element or rejection per line.
+impl fmt::Display for Trace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for traced in &self.elements {
+            write!(
+                f,
+                "{}: {:?} ({}, {:?})",
+                traced.element.kind.as_str(),
+                traced.element.value(),
+                traced.rule.as_str(),
+                traced.element.confidence,
+            )?;
+            let values = traced
+                .tokens
+                .iter()
+                .map(|&index| self.tokens[index].value)
+                .collect::<Vec<_>>();
+            if !values.is_empty() {
+                write!(f, " from {values:?}")?;
+            }
+            writeln!(f)?;
+        }
+        for rejection in &self.rejected {
+            writeln!(
+                f,
+                "Rejected {:?} ({}): {}",
+                self.tokens[rejection.token].value,
+                rejection.rule.as_str(),
+                rejection.reason,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A single application of a rule
+#[derive(Debug)]
+struct Step {
+    rule: Rule,
+    consumed: Vec<usize>,
+}
+
+/// Records the rule behind each element while parsing.
+///
+/// A disabled tracer records nothing, which is what every parse except
+/// [`parse_traced`](crate::parse_traced) uses.
+#[derive(Debug, Default)]
+pub(crate) struct Tracer {
+    enabled: bool,
+    known: Vec<bool>,
+    start: usize,
+    /// The step that produced each element, in the order the elements were pushed
+    origins: Vec<usize>,
+    steps: Vec<Step>,
+    rejected: Vec<Rejection>,
+}
+
+impl Tracer {
+    pub(crate) fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    /// Starts a step, taking note of the tokens that are already known
+    pub(crate) fn begin(&mut self, tokens: &[Token<'_>], results: &[Element<'_>]) {
+        if !self.enabled {
+            return;
+        }
+        self.known.clear();
+        self.known.extend(tokens.iter().map(|t| t.is_identified()));
+        self.start = results.len();
+    }
+
+    /// Ends a step, attributing the elements pushed since it began to the rule
+    pub(crate) fn end(&mut self, rule: Rule, tokens: &[Token<'_>], results: &[Element<'_>]) {
+        if !self.enabled || results.len() == self.start {
+            return;
+        }
+        let consumed = tokens
+            .iter()
+            .zip(&self.known)
+            .enumerate()
+            .filter_map(|(index, (token, known))| {
+                (token.is_identified() && !known).then_some(index)
+            })
+            .collect();
+        self.origins.extend(core::iter::repeat_n(
+            self.steps.len(),
+            results.len() - self.start,
+        ));
+        self.steps.push(Step { rule, consumed });
+        self.start = results.len();
+    }
+
+    /// Records a candidate token that was rejected by a rule
+    pub(crate) fn reject(&mut self, rule: Rule, token: &Token<'_>, reason: &'static str) {
+        if self.enabled {
+            self.rejected.push(Rejection {
+                rule,
+                token: token.position,
+                reason,
+            });
+        }
+    }
+
+    /// Builds the trace from the tokens of the name and the unsorted elements
+    pub(crate) fn finish<'a>(self, tokens: Vec<Token<'a>>, results: Vec<Element<'a>>) -> Trace<'a> {
+        let mut elements = results
+            .into_iter()
+            .zip(self.origins)
+            .map(|(element, origin)| {
+                let step = &self.steps[origin];
+                let tokens = step
+                    .consumed
+                    .iter()
+                    .copied()
+                    .filter(|&index| {
+                        !step.rule.is_per_token() || {
+                            let span = tokens[index].span();
+                            span.start < element.span.end && element.span.start < span.end
+                        }
+                    })
+                    .collect();
+                TracedElement {
+                    element,
+                    rule: step.rule,
+                    tokens,
+                }
+            })
+            .collect::<Vec<_>>();
+        elements.sort_by_key(|e| e.element.position);
+        Trace {
+            tokens,
+            elements,
+            rejected: self.rejected,
+        }
+    }
+}
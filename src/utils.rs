@@ -37,6 +37,187 @@ pub(crate) fn from_roman_number(s: &str) -> Option<&'static str> {
     LOOKUP.get(s).copied()
 }
 
+pub(crate) fn is_japanese_number(ch: char) -> bool {
+    matches!(
+        ch,
+        '〇' | '一' | '二' | '三' | '四' | '五' | '六' | '七' | '八' | '九' | '十' | '百' | '千'
+    )
+}
+
+const fn from_fullwidth_digit(ch: char) -> Option<u32> {
+    match ch {
+        '０'..='９' => Some(ch as u32 - '０' as u32),
+        _ => None,
+    }
+}
+
+/// Parses a number made up of ASCII or fullwidth digits (e.g. `01` or `０１`)
+fn parse_digits(s: &str) -> Option<u32> {
+    if s.is_empty() {
+        return None;
+    }
+    s.chars().try_fold(0u32, |acc, ch| {
+        let digit = ch.to_digit(10).or_else(|| from_fullwidth_digit(ch))?;
+        acc.checked_mul(10)?.checked_add(digit)
+    })
+}
+
+/// Parses kanji numerals in either positional (e.g. `二〇`) or multiplicative (e.g. `二十`) form.
+///
+/// In the multiplicative form the multipliers have to be in decreasing order with at most a
+/// single digit in front of each, so malformed numbers such as `十十` are rejected.
+fn parse_japanese_number(s: &str) -> Option<u32> {
+    if s.is_empty() || !s.chars().all(is_japanese_number) {
+        return None;
+    }
+
+    let digit = |ch| {
+        "〇一二三四五六七八九"
+            .chars()
+            .position(|x| x == ch)
+            .map(|x| x as u32)
+    };
+    if s.chars().all(|ch| digit(ch).is_some()) {
+        return s
+            .chars()
+            .try_fold(0u32, |acc, ch| acc.checked_mul(10)?.checked_add(digit(ch)?));
+    }
+
+    let mut total = 0;
+    let mut current: Option<u32> = None;
+    let mut last_multiplier = u32::MAX;
+    for ch in s.chars() {
+        let multiplier = match ch {
+            '十' => 10,
+            '百' => 100,
+            '千' => 1000,
+            _ => {
+                // Only a single non-zero digit can come before a multiplier or at the end
+                match (current, digit(ch)?) {
+                    (None, value @ 1..) => current = Some(value),
+                    _ => return None,
+                }
+                continue;
+            }
+        };
+        if multiplier >= last_multiplier {
+            return None;
+        }
+        last_multiplier = multiplier;
+        total += current.take().unwrap_or(1) * multiplier;
+    }
+    Some(total + current.unwrap_or_default())
+}
+
+/// Parses an uppercase roman numeral in its canonical form (e.g. `XIV` but not `XIIII`)
+pub(crate) fn parse_roman_number(s: &str) -> Option<u32> {
+    const NUMERALS: [(&str, u32); 13] = [
+        ("M", 1000),
+        ("CM", 900),
+        ("D", 500),
+        ("CD", 400),
+        ("C", 100),
+        ("XC", 90),
+        ("L", 50),
+        ("XL", 40),
+        ("X", 10),
+        ("IX", 9),
+        ("V", 5),
+        ("IV", 4),
+        ("I", 1),
+    ];
+
+    if s.is_empty() {
+        return None;
+    }
+
+    // Greedily consume the numerals from largest to smallest, which only
+    // succeeds for numbers written in their canonical form
+    let mut rest = s;
+    let mut total = 0u32;
+    for (index, (numeral, value)) in NUMERALS.iter().enumerate() {
+        // Only M can repeat more than 3 times, the subtractive forms never repeat
+        let max = match index {
+            0 => usize::MAX,
+            _ if numeral.len() == 2 || *value == 5 || *value == 50 || *value == 500 => 1,
+            _ => 3,
+        };
+        let mut count = 0;
+        while count < max {
+            match rest.strip_prefix(numeral) {
+                Some(remaining) => {
+                    rest = remaining;
+                    total = total.checked_add(*value)?;
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+    }
+    rest.is_empty().then_some(total)
+}
+
+/// Parses ordinal numbers such as `2nd`, `11th`, `Third` or `Twelfth`
+pub(crate) fn parse_ordinal_number(s: &str) -> Option<u32> {
+    static LOOKUP: phf::Map<&'static UncasedStr, u32> = phf_map! {
+        UncasedStr::new("First") =>       1,
+        UncasedStr::new("Second") =>      2,
+        UncasedStr::new("Third") =>       3,
+        UncasedStr::new("Fourth") =>      4,
+        UncasedStr::new("Fifth") =>       5,
+        UncasedStr::new("Sixth") =>       6,
+        UncasedStr::new("Seventh") =>     7,
+        UncasedStr::new("Eighth") =>      8,
+        UncasedStr::new("Ninth") =>       9,
+        UncasedStr::new("Tenth") =>       10,
+        UncasedStr::new("Eleventh") =>    11,
+        UncasedStr::new("Twelfth") =>     12,
+        UncasedStr::new("Thirteenth") =>  13,
+        UncasedStr::new("Fourteenth") =>  14,
+        UncasedStr::new("Fifteenth") =>   15,
+        UncasedStr::new("Sixteenth") =>   16,
+        UncasedStr::new("Seventeenth") => 17,
+        UncasedStr::new("Eighteenth") =>  18,
+        UncasedStr::new("Nineteenth") =>  19,
+        UncasedStr::new("Twentieth") =>   20,
+    };
+
+    if let Some(number) = LOOKUP.get(UncasedStr::new(s)) {
+        return Some(*number);
+    }
+
+    let index = s.len().checked_sub(2)?;
+    if !s.is_char_boundary(index) {
+        return None;
+    }
+    let (number, suffix) = s.split_at(index);
+    let is_suffix = ["st", "nd", "rd", "th"]
+        .iter()
+        .any(|x| suffix.eq_ignore_ascii_case(x));
+    if is_suffix && number.bytes().all(|b| b.is_ascii_digit()) {
+        number.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parses a number made up of digits (e.g. `01` or `０１`) or kanji numerals (e.g. `十二`).
+///
+/// Unlike [`parse_number`], words that only look like numbers (e.g. `MIX` or `CD`) are rejected.
+pub(crate) fn parse_numerals(s: &str) -> Option<u32> {
+    parse_digits(s).or_else(|| parse_japanese_number(s))
+}
+
+/// Converts a string that represents a number into its numeric value.
+///
+/// This supports ASCII and fullwidth digits (e.g. `01`, `０１`), kanji numerals
+/// (e.g. `十二`), roman numerals (e.g. `XII`) and ordinal numbers (e.g. `12th`, `Twelfth`).
+pub(crate) fn parse_number(s: &str) -> Option<u32> {
+    parse_numerals(s)
+        .or_else(|| parse_roman_number(s))
+        .or_else(|| parse_ordinal_number(s))
+}
+
 // This is borrowed and modified from the stdlib
 // FIXME: Remove when MSRV is bumped to 1.77 (currently 1.74)
 pub(crate) fn last_chunk_mut<const N: usize, T>(slice: &mut [T]) -> Option<&mut [T; N]> {
@@ -139,3 +320,45 @@ pub(crate) fn windows_mut<T, const SIZE: usize>(slice: &mut [T]) -> WindowsMut<'
     assert_ne!(SIZE, 0);
     WindowsMut { slice, start: 0 }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        let cases = [
+            ("01", Some(1)),
+            ("２０１２", Some(2012)),
+            ("二", Some(2)),
+            ("十二", Some(12)),
+            ("二十", Some(20)),
+            ("百二十三", Some(123)),
+            ("二〇", Some(20)),
+            ("II", Some(2)),
+            ("XIV", Some(14)),
+            ("MCMXCIX", Some(1999)),
+            ("IIII", None),
+            ("IC", None),
+            ("2nd", Some(2)),
+            ("11th", Some(11)),
+            ("Twelfth", Some(12)),
+            ("1.5", None),
+            ("", None),
+            ("九九九九九九九九九九九", None),
+            ("十十", None),
+            ("二十十", None),
+            ("十百", None),
+            ("二二十", None),
+            ("〇十", None),
+            ("十二三", None),
+            ("千九百九十九", Some(1999)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_number(input), expected, "{input}");
+        }
+
+        // Numbers that overflow are rejected rather than wrapping around
+        assert_eq!(parse_number(&"M".repeat(5_000_000)), None);
+    }
+}
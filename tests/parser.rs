@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use anitomy::ElementKind;
+use anitomy::{Confidence, ElementKind};
 use serde::{
     de::{value::SeqAccessDeserializer, Visitor},
     Deserialize,
@@ -197,3 +197,377 @@ fn test_json_data() {
         }
     }
 }
+
+#[test]
+fn test_element_spans() {
+    let input = "[TaigaSubs]_Toradora!_(2008)_-_01v2_-_Tiger_and_Dragon_[1280x720_H.264_FLAC][1234ABCD].mkv";
+    let expected = [
+        (ElementKind::ReleaseGroup, "TaigaSubs"),
+        (ElementKind::Title, "Toradora!"),
+        (ElementKind::Year, "2008"),
+        (ElementKind::Episode, "01"),
+        (ElementKind::ReleaseVersion, "2"),
+        (ElementKind::EpisodeTitle, "Tiger_and_Dragon"),
+        (ElementKind::VideoResolution, "1280x720"),
+        (ElementKind::VideoTerm, "H.264"),
+        (ElementKind::AudioTerm, "FLAC"),
+        (ElementKind::FileChecksum, "1234ABCD"),
+        (ElementKind::FileExtension, "mkv"),
+    ];
+    let actual = anitomy::parse(input)
+        .into_iter()
+        .map(|e| (e.kind(), &input[e.span()]))
+        .collect::<Vec<_>>();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_element_numbers() {
+    let cases = [
+        (
+            "[Group] Title 第二期 第十二話.mkv",
+            ElementKind::Season,
+            "二",
+            2,
+        ),
+        (
+            "[Group] Title 第二期 第十二話.mkv",
+            ElementKind::Episode,
+            "十二",
+            12,
+        ),
+        ("Title ２期 - 01 [720p]", ElementKind::Season, "２", 2),
+        (
+            "Title Season XII - 01 [720p]",
+            ElementKind::Season,
+            "XII",
+            12,
+        ),
+        (
+            "Title 12th Season - 01 [720p]",
+            ElementKind::Season,
+            "12th",
+            12,
+        ),
+        (
+            "Title Eleventh Season - 01 [720p]",
+            ElementKind::Season,
+            "Eleventh",
+            11,
+        ),
+        ("Title 2nd Season - 01 [720p]", ElementKind::Season, "2", 2),
+    ];
+    for (input, kind, value, number) in cases {
+        let elements = anitomy::parse(input);
+        let element = elements
+            .iter()
+            .find(|e| e.kind() == kind)
+            .unwrap_or_else(|| panic!("{input}: missing {kind:?}"));
+        assert_eq!(element.value(), value, "{input}");
+        assert_eq!(element.number(), Some(number), "{input}");
+    }
+
+    // Large roman numerals are a part of the title rather than a season
+    for (input, title) in [
+        ("[Group] Title Season C - 03.mkv", "Title Season C"),
+        ("Title Season MCMXCIX - 03.mkv", "Title Season MCMXCIX"),
+    ] {
+        let elements = anitomy::parse(input);
+        assert!(
+            elements.iter().all(|e| e.kind() != ElementKind::Season),
+            "{input}"
+        );
+        let found = elements.iter().find(|e| e.kind() == ElementKind::Title);
+        assert_eq!(found.map(|e| e.value()), Some(title), "{input}");
+    }
+}
+
+#[test]
+fn test_episode_lists() {
+    let episodes = |input: &str| -> Vec<String> {
+        anitomy::parse(input)
+            .iter()
+            .filter(|e| e.kind() == ElementKind::Episode)
+            .map(|e| e.value().to_owned())
+            .collect()
+    };
+
+    assert_eq!(
+        episodes("Title - 01, 03, 05 [720p].mkv"),
+        ["01", "03", "05"]
+    );
+    assert_eq!(episodes("Title - 9, 10, 11.mkv"), ["9", "10", "11"]);
+    // Numbers that aren't increasing, are too far apart or use another format end the list
+    assert_eq!(episodes("Title - 05, 03.mkv"), ["05"]);
+    assert_eq!(episodes("Title - 01, 45 [720p].mkv"), ["01"]);
+    assert_eq!(episodes("Title - 01, 2, 3 [720p].mkv"), ["01"]);
+
+    let elements = anitomy::parse("Title - 01, 2011.mkv");
+    let found = elements
+        .iter()
+        .find(|e| e.kind() == ElementKind::EpisodeTitle);
+    assert_eq!(found.map(|e| e.value()), Some("2011"));
+    assert_eq!(episodes("Title - 01, 2011.mkv"), ["01"]);
+}
+
+#[test]
+fn test_element_confidence() {
+    let cases = [
+        (
+            "[Group] Title S01E03 [720p].mkv",
+            ElementKind::Episode,
+            Confidence::High,
+        ),
+        (
+            "[Group] Title - 03 [720p].mkv",
+            ElementKind::Episode,
+            Confidence::High,
+        ),
+        (
+            "[Group] Title (03) [720p].mkv",
+            ElementKind::Episode,
+            Confidence::Medium,
+        ),
+        ("Title 03 [720p].mkv", ElementKind::Episode, Confidence::Low),
+        (
+            "[Group] Title - 01 [ESP].mkv",
+            ElementKind::Language,
+            Confidence::Medium,
+        ),
+        (
+            "[Group] Title - 01 [x264].mkv",
+            ElementKind::VideoTerm,
+            Confidence::High,
+        ),
+        (
+            "Title - 01 - Episode Title.mkv",
+            ElementKind::EpisodeTitle,
+            Confidence::Medium,
+        ),
+    ];
+    for (input, kind, confidence) in cases {
+        let elements = anitomy::parse(input);
+        let element = elements
+            .iter()
+            .find(|e| e.kind() == kind)
+            .unwrap_or_else(|| panic!("{input}: missing {kind:?}"));
+        assert_eq!(element.confidence(), confidence, "{input}");
+    }
+}
+
+#[test]
+fn test_parse_alternatives() {
+    let input = "Mob Psycho 100 [720p].mkv";
+    let candidates = anitomy::parse_alternatives(input, 5);
+    assert_eq!(candidates[0], anitomy::parse(input));
+
+    let readings = candidates
+        .iter()
+        .map(|elements| {
+            let title = elements.iter().find(|e| e.kind() == ElementKind::Title);
+            let episode = elements.iter().find(|e| e.kind() == ElementKind::Episode);
+            (title.map(|e| e.value()), episode.map(|e| e.value()))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        readings,
+        [
+            (Some("Mob Psycho"), Some("100")),
+            (Some("Mob Psycho 100"), None),
+        ]
+    );
+
+    assert!(anitomy::parse_alternatives(input, 0).is_empty());
+    assert_eq!(anitomy::parse_alternatives(input, 1).len(), 1);
+
+    // The trailing number is still an alternative with a lot of ambiguous keywords
+    let input = "Tokyo ESP Opus Android ITA Final PV 100 [720p].mkv";
+    let candidates = anitomy::parse_alternatives(input, 2);
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates[1]
+        .iter()
+        .all(|e| e.kind() != ElementKind::Episode));
+}
+
+#[test]
+fn test_runtime_keywords() {
+    use anitomy::{Keyword, KeywordKind, Parser};
+
+    let input = "[Group] Title CR - 01 [FooSub BD 1080p].mkv";
+    let mut parser = Parser::new();
+    let keywords = parser.keywords_mut();
+    keywords.insert("CR", Keyword::ambiguous(KeywordKind::Source));
+    keywords.insert("foosub", Keyword::new(KeywordKind::Subtitles));
+    keywords.disable("BD");
+    assert_eq!(
+        keywords.get("cr"),
+        Some(Keyword::ambiguous(KeywordKind::Source))
+    );
+    assert_eq!(keywords.get("BD"), None);
+
+    let elements = parser.parse(input);
+    let find = |kind| {
+        elements
+            .iter()
+            .filter(|e| e.kind() == kind)
+            .map(|e| e.value())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(find(ElementKind::Title), ["Title CR"]);
+    assert_eq!(find(ElementKind::Source), ["CR"]);
+    assert_eq!(find(ElementKind::Subtitles), ["FooSub"]);
+
+    parser.keywords_mut().reset("BD");
+    assert_eq!(
+        parser.keywords_mut().get("BD"),
+        Some(Keyword::new(KeywordKind::Source))
+    );
+
+    // The default parser is unaffected
+    let elements = anitomy::parse(input);
+    assert!(elements
+        .iter()
+        .any(|e| e.kind() == ElementKind::Title && e.value() == "Title"));
+    // Release versions only have a leading `v` stripped
+    let mut parser = Parser::new();
+    let keywords = parser.keywords_mut();
+    keywords.insert("第2版", Keyword::new(KeywordKind::ReleaseVersion));
+    keywords.insert("Final", Keyword::new(KeywordKind::ReleaseVersion));
+    for (input, expected) in [
+        ("[Group] Title - 01 [第2版].mkv", "第2版"),
+        ("[Group] Title - 01 [Final].mkv", "Final"),
+        ("[Group] Title - 01 [V2].mkv", "2"),
+    ] {
+        let elements = parser.parse(input);
+        let version = elements
+            .iter()
+            .find(|e| e.kind() == ElementKind::ReleaseVersion)
+            .map(|e| e.value());
+        assert_eq!(version, Some(expected), "{input}");
+    }
+}
+
+#[test]
+fn test_parse_batch() {
+    let names = [
+        "[Group] Tokyo ESP - 01 [720p].mkv",
+        "[Group] Tokyo ESP - 02 [720p].mkv",
+        "[Group] Tokyo ESP - 03 [720p].mkv",
+        "Group_Tokyo_ESP_04.mkv",
+        "[Group] Tokyo ESP 1080 [1080p].mkv",
+    ];
+    let results = anitomy::parse_batch(&names);
+    assert_eq!(results.len(), names.len());
+
+    let readings = results
+        .iter()
+        .map(|elements| {
+            let find = |kind| {
+                elements
+                    .iter()
+                    .find(|e| e.kind() == kind)
+                    .map(|e| e.value())
+            };
+            (find(ElementKind::Title), find(ElementKind::Episode))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        readings,
+        [
+            (Some("Tokyo ESP"), Some("01")),
+            (Some("Tokyo ESP"), Some("02")),
+            (Some("Tokyo ESP"), Some("03")),
+            (Some("Tokyo ESP"), Some("04")),
+            (Some("Tokyo ESP"), Some("1080")),
+        ]
+    );
+
+    // Numbers that are already read as something else are not episodes
+    let find_all = |names: &[&str], kind| {
+        anitomy::parse_batch(names)
+            .iter()
+            .map(|elements| {
+                elements
+                    .iter()
+                    .find(|e| e.kind() == kind)
+                    .map(|e| e.value().to_owned())
+            })
+            .collect::<Vec<_>>()
+    };
+    let volumes = [
+        "[Group] Title Vol.01 [BD 1080p].mkv",
+        "[Group] Title Vol.02 [BD 1080p].mkv",
+        "[Group] Title Vol.03 [BD 1080p].mkv",
+    ];
+    assert_eq!(find_all(&volumes, ElementKind::Episode), [None, None, None]);
+    assert_eq!(
+        find_all(&volumes, ElementKind::Volume),
+        [Some("01".into()), Some("02".into()), Some("03".into())]
+    );
+    let seasons = [
+        "[Group] Title Season 1 - 05.mkv",
+        "[Group] Title Season 2 - 05.mkv",
+        "[Group] Title Season 3 - 05.mkv",
+    ];
+    assert_eq!(
+        find_all(&seasons, ElementKind::Episode),
+        [Some("05".into()), Some("05".into()), Some("05".into())]
+    );
+
+    let sequels = [
+        "[Group] Rocky I.mkv",
+        "[Group] Rocky II.mkv",
+        "[Group] Rocky III.mkv",
+    ];
+    assert_eq!(find_all(&sequels, ElementKind::Episode), [None, None, None]);
+    assert_eq!(
+        find_all(&sequels, ElementKind::Title)[1],
+        Some("Rocky II".into())
+    );
+
+    // A single name is parsed as usual
+    let name = "[Group] Show Name 2 05.mkv";
+    assert_eq!(anitomy::parse_batch(&[name]), [anitomy::parse(name)]);
+}
+
+#[test]
+fn test_parse_traced() {
+    use anitomy::Rule;
+
+    let inputs = [
+        "[TaigaSubs]_Toradora!_(2008)_-_01v2_-_Tiger_and_Dragon_[1280x720_H.264_FLAC][1234ABCD].mkv",
+        "[Group] Title S01E03 [1080p].mkv",
+        "Evangelion 2.22 Movie 3 - 05-02.mkv",
+    ];
+    for input in inputs {
+        let trace = anitomy::parse_traced(input);
+        assert_eq!(
+            trace.clone().into_elements(),
+            anitomy::parse(input),
+            "{input}"
+        );
+    }
+
+    let trace = anitomy::parse_traced(inputs[1]);
+    let episode = trace
+        .elements()
+        .iter()
+        .find(|e| e.element().kind() == ElementKind::Episode)
+        .unwrap();
+    assert_eq!(episode.rule(), Rule::SeasonAndEpisode);
+    let consumed = episode
+        .tokens()
+        .iter()
+        .map(|&index| trace.tokens()[index].value())
+        .collect::<Vec<_>>();
+    assert_eq!(consumed, ["S01E03"]);
+
+    let trace = anitomy::parse_traced(inputs[2]);
+    let rejected = trace
+        .rejected()
+        .iter()
+        .map(|r| (r.rule(), trace.tokens()[r.token()].value()))
+        .collect::<Vec<_>>();
+    assert!(rejected.contains(&(Rule::MultiEpisode, "05-02")));
+    assert!(rejected.contains(&(Rule::LastNumber, "3")));
+}